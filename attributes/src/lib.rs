@@ -5,30 +5,373 @@ use proc_macro::*;
 use quote::format_ident;
 use quote::quote;
 
-use syn::{Expr, ExprReturn, FnArg, ReturnType, Stmt};
+use syn::{Expr, ExprReturn, FnArg, Meta, NestedMeta, ReturnType, Stmt};
 
-fn handler(
-    _attr: TokenStream,
-    item: TokenStream,
-    defer_return: quote::__private::TokenStream,
-) -> TokenStream {
-    // There is _probably_ a more efficient way to do what I want to do, but hey I am here
-    // to learn so why not join me on my quest to create this procedural macro...lol
-    let mut defer = false;
+// Which macro a handler is expanded by
+#[derive(Clone, Copy, PartialEq)]
+enum HandlerKind {
+    SlashCommand,
+    Component,
+}
+
+impl HandlerKind {
+    // How the handler defers when `#[defer]` has no arguments
+    fn default_defer(self) -> quote::__private::TokenStream {
+        match self {
+            HandlerKind::SlashCommand => quote!(::rusty_interaction::types::interaction::InteractionResponseType::DefferedChannelMessageWithSource),
+            HandlerKind::Component => quote!(
+                ::rusty_interaction::types::interaction::InteractionResponseType::DefferedUpdateMessage
+            ),
+        }
+    }
+}
+
+// How the handler should defer, as set with `#[defer(...)]`
+struct DeferOptions {
+    // Overrides the default deferral response type of the handler
+    rtype: Option<quote::__private::TokenStream>,
+    ephemeral: bool,
+}
+
+fn parse_defer(at: &syn::Attribute, kind: HandlerKind) -> syn::Result<DeferOptions> {
+    let mut opts = DeferOptions {
+        rtype: None,
+        ephemeral: false,
+    };
+    let mut update = false;
+    let mut reply = false;
+
+    match at.parse_meta()? {
+        Meta::Path(_) => (),
+        Meta::List(list) => {
+            for arg in &list.nested {
+                let ident = match arg {
+                    NestedMeta::Meta(Meta::Path(p)) => p.get_ident().map(|i| i.to_string()),
+                    _ => None,
+                };
+                match ident.as_deref() {
+                    Some("ephemeral") => opts.ephemeral = true,
+                    Some("update") => update = true,
+                    Some("reply") => reply = true,
+                    _ => {
+                        return Err(syn::Error::new_spanned(
+                            arg,
+                            "unknown `defer` argument, expected `ephemeral`, `update` or `reply`",
+                        ))
+                    }
+                }
+            }
+        }
+        Meta::NameValue(nv) => {
+            return Err(syn::Error::new_spanned(
+                nv,
+                "expected `#[defer]` or `#[defer(...)]` with `ephemeral`, `update` or `reply`",
+            ))
+        }
+    }
+
+    if update && reply {
+        return Err(syn::Error::new_spanned(
+            at,
+            "`update` and `reply` cannot be combined",
+        ));
+    }
+    if update && kind == HandlerKind::SlashCommand {
+        return Err(syn::Error::new_spanned(
+            at,
+            "`update` is only valid for component handlers, a slash command has no message to update",
+        ));
+    }
+    if update && opts.ephemeral {
+        return Err(syn::Error::new_spanned(
+            at,
+            "`update` does not send a new message, so it cannot be ephemeral. Use `#[defer(ephemeral)]` instead",
+        ));
+    }
+
+    if update {
+        opts.rtype = Some(quote!(
+            ::rusty_interaction::types::interaction::InteractionResponseType::DefferedUpdateMessage
+        ));
+    } else if reply || opts.ephemeral {
+        // An ephemeral deferral always sends a new "thinking..." message
+        opts.rtype = Some(quote!(::rusty_interaction::types::interaction::InteractionResponseType::DefferedChannelMessageWithSource));
+    }
+
+    Ok(opts)
+}
+
+// Builds the `Cooldown` set with `#[cooldown(...)]`
+fn parse_cooldown(at: &syn::Attribute) -> syn::Result<quote::__private::TokenStream> {
+    let usage = "expected `#[cooldown(seconds, user|guild|channel|global, exempt = \"path\", message = \"path\")]`";
+
+    let list = match at.parse_meta()? {
+        Meta::List(list) => list,
+        m => return Err(syn::Error::new_spanned(m, usage)),
+    };
+
+    let mut seconds = None;
+    let mut bucket = quote!(User);
+    let mut extra = quote!();
+
+    for arg in &list.nested {
+        match arg {
+            NestedMeta::Lit(syn::Lit::Int(i)) if seconds.is_none() => {
+                seconds = Some(i.base10_parse::<u64>()?);
+            }
+            NestedMeta::Meta(Meta::Path(p)) => {
+                let ident = p.get_ident().map(|i| i.to_string());
+                bucket = match ident.as_deref() {
+                    Some("user") => quote!(User),
+                    Some("guild") => quote!(Guild),
+                    Some("channel") => quote!(Channel),
+                    Some("global") => quote!(Global),
+                    _ => return Err(syn::Error::new_spanned(
+                        p,
+                        "unknown cooldown bucket, expected `user`, `guild`, `channel` or `global`",
+                    )),
+                };
+            }
+            NestedMeta::Meta(Meta::NameValue(nv))
+                if nv.path.is_ident("exempt") || nv.path.is_ident("message") =>
+            {
+                let f: syn::Path = match &nv.lit {
+                    syn::Lit::Str(s) => s.parse()?,
+                    l => {
+                        return Err(syn::Error::new_spanned(
+                            l,
+                            "expected a function path as a string",
+                        ))
+                    }
+                };
+                let method = nv.path.get_ident();
+                extra.extend(quote!(.#method(#f)));
+            }
+            _ => return Err(syn::Error::new_spanned(arg, usage)),
+        }
+    }
+
+    let seconds = match seconds {
+        Some(s) => s,
+        None => {
+            return Err(syn::Error::new_spanned(
+                at,
+                "missing the cooldown duration in seconds, like `#[cooldown(30)]`",
+            ))
+        }
+    };
+
+    Ok(quote! {
+        ::rusty_interaction::cooldown::Cooldown::new(
+            ::std::time::Duration::from_secs(#seconds),
+            ::rusty_interaction::cooldown::CooldownBucket::#bucket,
+        )#extra
+    })
+}
+
+// Builds the check of a guard attribute like `#[guild_only]`, or `None` if `at` is not a guard
+fn parse_guard(at: &syn::Attribute) -> syn::Result<Option<quote::__private::TokenStream>> {
+    let guard = quote!(::rusty_interaction::guard);
+    let name = match at.path.get_ident() {
+        Some(i) => i.to_string(),
+        None => return Ok(None),
+    };
+
+    let check = match name.as_str() {
+        "guild_only" | "dm_only" | "owner_only" => {
+            if !matches!(at.parse_meta()?, Meta::Path(_)) {
+                return Err(syn::Error::new_spanned(
+                    at,
+                    format!("`{}` takes no arguments", name),
+                ));
+            }
+            let f = format_ident!("{}", name);
+            if name == "owner_only" {
+                quote!(#guard::owner_only(__ih, &__ctx))
+            } else {
+                quote!(#guard::#f(&__ctx))
+            }
+        }
+        "require_roles" | "require_permissions" => {
+            let args = match at.parse_meta()? {
+                Meta::List(list) if !list.nested.is_empty() => list.nested,
+                m => {
+                    return Err(syn::Error::new_spanned(
+                        m,
+                        format!("expected `#[{}(...)]` with at least one argument", name),
+                    ))
+                }
+            };
+
+            if name == "require_roles" {
+                let roles = args
+                    .iter()
+                    .map(|arg| match arg {
+                        NestedMeta::Lit(syn::Lit::Int(i)) => Ok(quote!(#i)),
+                        NestedMeta::Meta(Meta::Path(p)) => Ok(quote!(#p)),
+                        _ => Err(syn::Error::new_spanned(
+                            arg,
+                            "expected a role ID, or a path to a constant holding one",
+                        )),
+                    })
+                    .collect::<syn::Result<Vec<_>>>()?;
+                quote!(#guard::require_roles(&__ctx, &[#(::rusty_interaction::types::id::RoleId::from(#roles)),*]))
+            } else {
+                let perms = args
+                    .iter()
+                    .map(|arg| match arg {
+                        NestedMeta::Meta(Meta::Path(p)) if p.get_ident().is_some() => Ok(p),
+                        _ => Err(syn::Error::new_spanned(
+                            arg,
+                            "expected a permission name, like `MANAGE_GUILD`",
+                        )),
+                    })
+                    .collect::<syn::Result<Vec<_>>>()?;
+                quote!(#guard::require_permissions(&__ctx, #(::rusty_interaction::types::guild::Permissions::#perms)|*))
+            }
+        }
+        _ => return Ok(None),
+    };
+
+    Ok(Some(quote! {
+        if let Some(r) = #guard::__guard(#check, &__ctx) {
+            return r;
+        }
+    }))
+}
 
+// The parameters a handler function takes
+struct HandlerParams {
+    // Pattern and type of every parameter taken by value, in order. These are extracted with `FromContext`
+    extract: Vec<(Box<syn::Pat>, Box<syn::Type>)>,
+    // Pattern and referenced type of the `&InteractionHandler` parameter, if any
+    handler: Option<(Box<syn::Pat>, Box<syn::Type>)>,
+}
+
+// Whether the last segment of a type path is `name`
+fn is_type_named(ty: &syn::Type, name: &str) -> bool {
+    match ty {
+        syn::Type::Path(p) => p.path.segments.last().is_some_and(|seg| seg.ident == name),
+        _ => false,
+    }
+}
+
+// The `InteractionHandler` is the only parameter taken by reference, every other parameter is an extractor.
+// Only the last segment of the type path is checked, so fully qualified paths work.
+fn parse_params(sig: &syn::Signature) -> syn::Result<HandlerParams> {
+    let mut extract = Vec::new();
+    let mut handler = None;
+
+    for p in &sig.inputs {
+        let t = match p {
+            FnArg::Receiver(r) => {
+                return Err(syn::Error::new_spanned(
+                    r,
+                    "`self` arguments are not allowed. If you need to access data in your function, use the `State` extractor or the `InteractionHandler.data` field",
+                ))
+            }
+            FnArg::Typed(t) => t,
+        };
+
+        if let syn::Type::Reference(r) = &*t.ty {
+            if is_type_named(&r.elem, "Context") {
+                return Err(syn::Error::new_spanned(
+                    &t.ty,
+                    "`Context` must be taken by value",
+                ));
+            }
+            if !is_type_named(&r.elem, "InteractionHandler") {
+                return Err(syn::Error::new_spanned(
+                    &t.ty,
+                    "only `&InteractionHandler` can be taken by reference, other parameters are extracted by value with `FromContext`",
+                ));
+            }
+            if handler.is_some() {
+                return Err(syn::Error::new_spanned(
+                    t,
+                    "expected at most one `&InteractionHandler` parameter",
+                ));
+            }
+            if r.mutability.is_some() {
+                return Err(syn::Error::new_spanned(
+                    &t.ty,
+                    "handlers share the `InteractionHandler`, take it as `&InteractionHandler`. Use `InteractionHandler::add_state` for data that changes",
+                ));
+            }
+            handler = Some((t.pat.clone(), r.elem.clone()));
+        } else if is_type_named(&t.ty, "InteractionHandler") {
+            return Err(syn::Error::new_spanned(
+                &t.ty,
+                "cannot take ownership of `InteractionHandler`, try using `&InteractionHandler`",
+            ));
+        } else {
+            extract.push((t.pat.clone(), t.ty.clone()));
+        }
+    }
+
+    Ok(HandlerParams { extract, handler })
+}
+
+// Attributes like `#[cooldown]` are read and removed by `#[slash_command]` and `#[component_handler]`.
+// They only expand by themselves when placed above those macros, where they would be ignored
+fn misplaced(name: &str, item: TokenStream) -> TokenStream {
+    let msg = format!(
+        "`#[{}]` must be placed below #[slash_command] or #[component_handler]",
+        name
+    );
+    let mut out: TokenStream = syn::Error::new(Span::call_site().into(), msg)
+        .to_compile_error()
+        .into();
+
+    // Keep the function, so its uses don't cause more errors
+    out.extend(item);
+    out
+}
+
+fn handler(attr: TokenStream, item: TokenStream, kind: HandlerKind) -> TokenStream {
     // Parse the stream of tokens to something more usable.
     let input = syn::parse_macro_input!(item as syn::ItemFn);
 
+    expand_handler(attr.into(), input, kind)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+fn expand_handler(
+    attr: quote::__private::TokenStream,
+    input: syn::ItemFn,
+    kind: HandlerKind,
+) -> syn::Result<quote::__private::TokenStream> {
+    if !attr.is_empty() {
+        return Err(syn::Error::new_spanned(
+            attr,
+            "this attribute takes no arguments",
+        ));
+    }
+
     // Let's see if the programmer wants to respond with a deferring acknowlegdement first.
     // If so, the end-result needs to be built differently.
+    let mut defer: Option<DeferOptions> = None;
+    let mut cooldown = None;
+    let mut guards = Vec::new();
     for at in &input.attrs {
-        for seg in at.path.segments.clone() {
-            if seg.ident == "defer" {
-                defer = true;
-            }
+        if at.path.is_ident("defer") {
+            defer = Some(parse_defer(at, kind)?);
+        } else if at.path.is_ident("cooldown") {
+            cooldown = Some(parse_cooldown(at)?);
+        } else if let Some(g) = parse_guard(at)? {
+            guards.push(g);
         }
     }
 
+    let is_guard = |name| input.attrs.iter().any(|at| at.path.is_ident(name));
+    if is_guard("guild_only") && is_guard("dm_only") {
+        return Err(syn::Error::new_spanned(
+            &input.sig.ident,
+            "`guild_only` and `dm_only` cannot be combined",
+        ));
+    }
+
     // Ok here comes the fun part
 
     // Get the function name
@@ -36,160 +379,261 @@ fn handler(
     // Get the visibility (public fn, private fn, etc)
     let vis = &input.vis;
 
-    // Get the parameters and return types
-    let params = &input.sig.inputs;
-    let ret_sig = &input.sig.output;
-
-    // Must be filled later, but define its type for now.
-    let ret: syn::Type;
-
     // Get the function body
     let body = &input.block;
 
-    // Check for a proper return type and fill ret if found.
-    match ret_sig {
+    // Check for a proper return type
+    let ret = match &input.sig.output {
         ReturnType::Default => {
-            panic!("Expected an `Result<InteractionResponse, std::convert::Infallible>` return type, but got no return type. Consider adding `-> Result<InteractionResponse, Infallible>` to your function signature.");
+            return Err(syn::Error::new_spanned(
+                &input.sig,
+                "expected an `InteractionResponse` or `Result<InteractionResponse, E>` return type, but got no return type. Consider adding `-> InteractionResponse` to your function signature",
+            ));
         }
-        ReturnType::Type(_a, b) => {
-            ret = *b.clone();
+        ReturnType::Type(_a, b) => b,
+    };
+
+    let params = parse_params(&input.sig)?;
+    let pats: Vec<_> = params.extract.iter().map(|(pat, _)| pat).collect();
+    let tys: Vec<_> = params.extract.iter().map(|(_, ty)| ty).collect();
+    let (ih_pat, ih_ty) = match &params.handler {
+        Some((pat, ty)) => (quote!(#pat), quote!(#ty)),
+        None => (
+            quote!(_),
+            quote!(::rusty_interaction::handler::InteractionHandler),
+        ),
+    };
+
+    // Extracts a parameter, or returns the error reply
+    let extract = tys.iter().map(|ty| {
+        quote! {
+            match ::rusty_interaction::extract::__extract::<#ty>(__ih, &__ctx) {
+                Ok(v) => v,
+                Err(r) => return *r,
+            }
         }
-    }
-
-    // Find the name of the Context parameter
-    let mut ctxname: Option<syn::Ident> = None;
-    let mut handlename: Option<syn::Ident> = None;
-    // eprintln!("{:#?}", params);
-
-    // I am honestly laughing at this...
-    // But hey it works! :D
-    for p in params {
-        if let FnArg::Typed(t) = p {
-            match &*t.ty {
-                // This might be a Context
-                syn::Type::Path(b) => {
-                    for segment in b.path.segments.clone() {
-                        if segment.ident == "Context" {
-                            if let syn::Pat::Ident(a) = &*t.pat {
-                                ctxname = Some(a.ident.clone());
-                                break;
-                            }
-                        } else if segment.ident == "InteractionHandler" {
-                            panic!("Cannot take ownership of `InteractionHandler`. Try using &InteractionHandler!")
-                        }
-                    }
-                }
-                // This might be an &InteractionHandler!
-                syn::Type::Reference(r) => {
-                    let e = r.elem.clone();
-                    if let syn::Type::Path(w) = &*e {
-                        for segment in w.path.segments.clone() {
-                            if segment.ident == "InteractionHandler" {
-                                if let syn::Pat::Ident(a) = &*t.pat {
-                                    handlename = Some(a.ident.clone());
-                                    break;
-                                }
-                            }
-                        }
-                    }
-                }
-                _ => {
-                    continue;
-                }
+    });
+
+    // Guards and cooldowns are checked before extracting, so rejected users don't see extraction errors.
+    // Guards go first, so a rejected use doesn't start a cooldown. The cooldown set with `set_cooldown` comes before `#[cooldown]`
+    let cooldown = cooldown.map(|init| {
+        quote! {
+            static __COOLDOWN: ::std::sync::OnceLock<::rusty_interaction::cooldown::Cooldown> = ::std::sync::OnceLock::new();
+            if let Some(r) = __COOLDOWN.get_or_init(|| #init).check_response(&__ctx) {
+                return r;
             }
         }
-        if let FnArg::Receiver(_) = p {
-            panic!("`self` arguments are not allowed. If you need to access data in your function, use the `InteractionHandler.data` field and `InteractionHandler::add_data` method")
+    });
+    let cooldown = quote! {
+        if let Some(r) = __ih.__cooldown(&__ctx) {
+            return r;
         }
-    }
-
-    if ctxname.is_none() {
-        panic!("Couldn't determine the Context parameter. Make sure you take a `Context` as an argument");
-    }
-
-    let mut ih_n = quote!(_);
-
-    if handlename.is_some() {
-        ih_n = quote!(#handlename);
-    }
+        #cooldown
+    };
 
     // Using quasi-quoting to generate a new function. This is what will be the end function returned to the compiler.
-    if !defer {
-        // Build the function
-        let subst_fn = quote! {
-            #vis fn #fname (#ih_n: &mut InteractionHandler, #ctxname: Context) -> ::std::pin::Pin<::std::boxed::Box<dyn Send + ::std::future::Future<Output = #ret> + '_>>{
-                Box::pin(async move {
-                    #body
-                })
-            }
-        };
-        subst_fn.into()
-    }
-    // Deferring is requested, this will require a bit more manipulation.
-    else {
+    if let Some(opts) = defer {
+        // Deferring is requested, this will require a bit more manipulation.
         // Create two functions. One that actually does the work, and one that handles the threading.
+        // Parameters are extracted before deferring, so extraction errors can still be replied to.
 
         let act_fn = format_ident!("__actual_{}", fname);
 
-        let subst_fn = quote! {
-            fn #act_fn (#ih_n: &mut InteractionHandler, #ctxname: Context) -> ::std::pin::Pin<::std::boxed::Box<dyn Send + ::std::future::Future<Output = #ret> + '_>>{
+        let defer_type = opts.rtype.unwrap_or_else(|| kind.default_defer());
+        let ephemeral = if opts.ephemeral {
+            quote!(.is_ephemeral(true))
+        } else {
+            quote!()
+        };
+
+        Ok(quote! {
+            fn #act_fn (__ih: &#ih_ty, (#(#pats,)*): (#(#tys,)*)) -> ::std::pin::Pin<::std::boxed::Box<dyn Send + ::std::future::Future<Output = #ret> + '_>>{
                 Box::pin(async move {
+                    let #ih_pat: &#ih_ty = __ih;
                     #body
                 })
             }
-            #vis fn #fname (ihd: &mut InteractionHandler, ctx: Context) -> ::std::pin::Pin<::std::boxed::Box<dyn Send + ::std::future::Future<Output = #ret> + '_>>{
+            #vis fn #fname (__ih: &#ih_ty, __ctx: ::rusty_interaction::types::interaction::Context) -> ::std::pin::Pin<::std::boxed::Box<dyn Send + ::std::future::Future<Output = ::rusty_interaction::types::interaction::InteractionResponse> + '_>>{
                 Box::pin(async move {
-                    // TODO: Try to do this without cloning.
-                    let mut __ih_c = ihd.clone();
+                    use ::rusty_interaction::Builder;
+                    use ::rusty_interaction::types::interaction::{InteractionResponseType, WebhookMessage};
+
+                    #(#guards)*
+                    #cooldown
+                    let __args = (#(#extract,)*);
+
+                    let __deferred = __ctx.respond()
+                        .respond_type(#defer_type)
+                        #ephemeral
+                        .build();
+                    let __deferred = match __deferred {
+                        Ok(r) => r,
+                        Err(e) => {
+                            ::rusty_interaction::log::error!("Failed to build deferred response: {}", e);
+                            return __ctx.respond().none();
+                        }
+                    };
 
-                    ::rusty_interaction::actix::Arbiter::spawn(async move {
+                    let __on_error = __ih.__error_handler();
 
-                        let __response = #act_fn (&mut __ih_c, ctx.clone()).await;
-                        if let Ok(__r) = __response{
-                            if __r.r#type != InteractionResponseType::Pong && __r.r#type != InteractionResponseType::None{
-                                if let Err(i) = ctx.edit_original(&WebhookMessage::from(__r)).await{
-                                    ::rusty_interaction::log::error!("Editing original message failed: {:?}", i);
-                                }
+                    // The clone shares the routes, data and state of the handler
+                    let __ih_c = __ih.clone();
+
+                    ::rusty_interaction::handler::__spawn(async move {
+
+                        let __res = #act_fn (&__ih_c, __args).await;
+                        let __r = ::rusty_interaction::handler::__into_response(__res, __on_error, &__ctx);
+                        if __r.r#type != InteractionResponseType::Pong && __r.r#type != InteractionResponseType::None{
+                            if let Err(i) = __ctx.edit_original(&WebhookMessage::from(__r)).await{
+                                ::rusty_interaction::log::error!("Editing original message failed: {:?}", i);
                             }
                         }
-                        else{
-                            // Nothing
-                        }
-
 
                     });
 
-                    return InteractionResponseBuilder::default().respond_type(#defer_return).finish();
+                    return __deferred;
 
                 })
             }
-        };
-        subst_fn.into()
+        })
+    } else {
+        // Build the function
+        Ok(quote! {
+            #vis fn #fname (__ih: &#ih_ty, __ctx: ::rusty_interaction::types::interaction::Context) -> ::std::pin::Pin<::std::boxed::Box<dyn Send + ::std::future::Future<Output = ::rusty_interaction::types::interaction::InteractionResponse> + '_>>{
+                Box::pin(async move {
+                    #(#guards)*
+                    #cooldown
+                    #(let #pats: #tys = #extract;)*
+                    let __on_error = __ih.__error_handler();
+                    let #ih_pat: &#ih_ty = __ih;
+
+                    let __res: #ret = async move #body.await;
+                    ::rusty_interaction::handler::__into_response(__res, __on_error, &__ctx)
+                })
+            }
+        })
     }
 }
 
 #[proc_macro_attribute]
 /// Convenience procedural macro that allows you to bind an async function to the [`InteractionHandler`] for handling component interactions.
+///
+/// Takes the same parameters as [`macro@slash_command`].
 pub fn component_handler(attr: TokenStream, item: TokenStream) -> TokenStream {
-    let ret = quote!(
-        ::rusty_interaction::types::interaction::InteractionResponseType::DefferedUpdateMessage
-    );
-
-    handler(attr, item, ret)
+    handler(attr, item, HandlerKind::Component)
 }
 
 #[proc_macro_attribute]
 /// Convenience procedural macro that allows you to bind an async function to the [`InteractionHandler`]
+///
+/// The function may take an `&InteractionHandler`. Every other parameter is resolved with `FromContext` before the body runs,
+/// like `Context`, `Member`, `GuildId`, `State<T>` or `Options<T>`. If a parameter cannot be extracted, the user gets an ephemeral error reply.
+///
+/// The function returns an `InteractionResponse`, or a `Result<InteractionResponse, E>` for any error type that converts into a boxed error.
+/// Errors are logged and turned into a reply by the hook set with `InteractionHandler::on_error`.
+///
+/// ```ignore
+/// #[slash_command]
+/// async fn ban(ctx: Context, guild: GuildId, Options(args): Options<BanArgs>) -> InteractionResponse {
+///     // ...
+/// }
+/// ```
 pub fn slash_command(attr: TokenStream, item: TokenStream) -> TokenStream {
-    let ret = quote!(::rusty_interaction::types::interaction::InteractionResponseType::DefferedChannelMessageWithSource);
-
-    handler(attr, item, ret)
+    handler(attr, item, HandlerKind::SlashCommand)
 }
 
 #[proc_macro_attribute]
-/// Send out a deffered channel message response before doing work.
+/// Send out a deffered response before doing work. Must be placed below `#[slash_command]` or `#[component_handler]`.
+///
+/// By default, slash commands defer with a new message and component handlers defer with an update of the original message.
+/// This can be changed with arguments:
+/// - `#[defer(reply)]`: defer with a new message, showing a "thinking..." state
+/// - `#[defer(ephemeral)]`: like `reply`, but the message is only shown to the user that invoked the interaction
+/// - `#[defer(update)]`: defer with an update of the original message. Only valid with `#[component_handler]`
+///
+/// ```ignore
+/// #[slash_command]
+/// #[defer(ephemeral)]
+/// async fn secret(ctx: Context) -> Result<InteractionResponse, ResponseError> {
+///     // ...
+///     return ctx.respond().content("Only you can see this").build();
+/// }
+/// ```
 pub fn defer(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    item
+    misplaced("defer", item)
+}
+
+#[proc_macro_attribute]
+/// Limit how often a handler can be used. Must be placed below `#[slash_command]` or `#[component_handler]`.
+///
+/// Takes the cooldown in seconds, followed by optional arguments:
+/// - `user`, `guild`, `channel` or `global`: who shares a cooldown. Defaults to `user`
+/// - `exempt = "path"`: a `fn(&Context) -> bool`. Interactions for which it returns `true` are not limited
+/// - `message = "path"`: a `fn(Duration) -> String` building the reply to users on cooldown
+///
+/// ```ignore
+/// #[slash_command]
+/// #[cooldown(30, guild, exempt = "is_admin")]
+/// async fn expensive(ctx: Context) -> InteractionResponse {
+///     // ...
+/// }
+/// ```
+pub fn cooldown(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    misplaced("cooldown", item)
+}
+
+#[proc_macro_attribute]
+/// Only allow the handler to be used in guilds. Must be placed below `#[slash_command]` or `#[component_handler]`.
+///
+/// Like all guards, a rejected interaction gets an ephemeral reply explaining why.
+pub fn guild_only(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    misplaced("guild_only", item)
+}
+
+#[proc_macro_attribute]
+/// Only allow the handler to be used in DMs. Must be placed below `#[slash_command]` or `#[component_handler]`.
+pub fn dm_only(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    misplaced("dm_only", item)
+}
+
+#[proc_macro_attribute]
+/// Only allow owners added with `InteractionHandler::add_owner` to use the handler. Must be placed below `#[slash_command]` or `#[component_handler]`.
+pub fn owner_only(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    misplaced("owner_only", item)
+}
+
+#[proc_macro_attribute]
+/// Only allow members with all of the given roles to use the handler. Must be placed below `#[slash_command]` or `#[component_handler]`.
+///
+/// Takes role IDs, or paths to constants holding them.
+///
+/// ```ignore
+/// const MODERATOR: u64 = 123456789012345678;
+///
+/// #[slash_command]
+/// #[require_roles(MODERATOR)]
+/// async fn warn(ctx: Context) -> InteractionResponse {
+///     // ...
+/// }
+/// ```
+pub fn require_roles(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    misplaced("require_roles", item)
+}
+
+#[proc_macro_attribute]
+/// Only allow members with all of the given permissions to use the handler. Must be placed below `#[slash_command]` or `#[component_handler]`.
+///
+/// Takes the names of `Permissions` flags. Administrators are always allowed.
+///
+/// ```ignore
+/// #[slash_command]
+/// #[require_permissions(MANAGE_GUILD, BAN_MEMBERS)]
+/// async fn purge(ctx: Context) -> InteractionResponse {
+///     // ...
+/// }
+/// ```
+pub fn require_permissions(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    misplaced("require_permissions", item)
 }
 
 #[doc(hidden)]
@@ -297,7 +741,7 @@ pub fn slash_command_test(_attr: TokenStream, item: TokenStream) -> TokenStream
     if !defer {
         // Build the function
         let subst_fn = quote! {
-            #vis fn #fname (#ih_n: &mut InteractionHandler, #ctxname: Context) -> ::std::pin::Pin<::std::boxed::Box<dyn Send + ::std::future::Future<Output = #ret> + '_>>{
+            #vis fn #fname (#ih_n: &InteractionHandler, #ctxname: Context) -> ::std::pin::Pin<::std::boxed::Box<dyn Send + ::std::future::Future<Output = #ret> + '_>>{
                 Box::pin(async move {
                     #body
                 })
@@ -344,9 +788,9 @@ pub fn slash_command_test(_attr: TokenStream, item: TokenStream) -> TokenStream
         // The difference here being that the non-deffered function doesn't have to spawn a new thread that
         // does the actual work. Here we need it to reply with a deffered channel message.
         let subst_fn = quote! {
-            #vis fn #fname (#ih_n: &mut InteractionHandler, #ctxname: Context) -> ::std::pin::Pin<::std::boxed::Box<dyn Send + ::std::future::Future<Output = #ret> + '_>>{
+            #vis fn #fname (#ih_n: &InteractionHandler, #ctxname: Context) -> ::std::pin::Pin<::std::boxed::Box<dyn Send + ::std::future::Future<Output = #ret> + '_>>{
                 Box::pin(async move {
-                    actix::Arbiter::spawn(async move {
+                    actix::spawn(async move {
                         #(#nvec)*
                         if #expra.r#type != InteractionResponseType::Pong && #expra.r#type != InteractionResponseType::None{
                             if let Err(i) = #ctxname.edit_original(&WebhookMessage::from(#expra)).await{
@@ -356,7 +800,7 @@ pub fn slash_command_test(_attr: TokenStream, item: TokenStream) -> TokenStream
 
                     });
 
-                    return InteractionResponseBuilder::default().respond_type(InteractionResponseType::DefferedChannelMessageWithSource).finish();
+                    return InteractionResponse::new(InteractionResponseType::DefferedChannelMessageWithSource, None);
 
                 })
             }
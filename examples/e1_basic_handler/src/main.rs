@@ -3,6 +3,7 @@ extern crate rusty_interaction;
 
 use rusty_interaction::handler::InteractionHandler;
 use rusty_interaction::types::interaction::*;
+use rusty_interaction::Builder;
 
 // This key is needed for verifying incoming Interactions. This verification is mandatory.
 // You can find this key in the Discord Developer Portal.
@@ -13,13 +14,14 @@ const APP_ID: u64 = 0;
 // This macro will transform the function to something the handler can use
 #[slash_command]
 // Function handlers should take an `Interaction` object and should return an `InteractionResponse`
-async fn test(ctx: Context) -> Result<InteractionResponse, std::convert::Infallible> {
+async fn test(ctx: Context) -> Result<InteractionResponse, ResponseError> {
     println!("I HAVE BEEN SUMMONED!!!");
 
     // Return a response by using the `Context.respond` function.
     // `Context.respond` returns an `InteractionResponseBuilder`.
     // You can now build a `InteractionResponse` by using it's functions.
-    ctx.respond().message("I was summoned?").finish()
+    // If building fails, the error is logged and the user gets an error reply.
+    ctx.respond().message("I was summoned?").build()
 }
 
 // The lib uses actix-web
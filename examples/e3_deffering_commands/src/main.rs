@@ -3,6 +3,7 @@ extern crate rusty_interaction;
 
 use rusty_interaction::handler::InteractionHandler;
 use rusty_interaction::types::interaction::*;
+use rusty_interaction::Builder;
 
 use async_std::task;
 use std::time::Duration;
@@ -14,13 +15,13 @@ const APP_ID: u64 = 0;
 #[slash_command]
 // Sending a deffered response by adding the `#[defer]` attribute
 #[defer]
-async fn test(ctx: Context) -> Result<InteractionResponse, std::convert::Infallible> {
+async fn test(ctx: Context) -> Result<InteractionResponse, ResponseError> {
     println!("I HAVE BEEN SUMMONED!!!");
 
     // This is representing some work that needs to be done before a response can be made
     task::sleep(Duration::from_secs(5)).await;
 
-    return ctx.respond().message("I was summoned?").finish();
+    return ctx.respond().message("I was summoned?").build();
 }
 
 #[actix_web::main]
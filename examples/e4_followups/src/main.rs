@@ -3,6 +3,7 @@ extern crate rusty_interaction;
 
 use rusty_interaction::handler::InteractionHandler;
 use rusty_interaction::types::interaction::*;
+use rusty_interaction::Builder;
 
 use async_std::task;
 use std::time::Duration;
@@ -11,7 +12,7 @@ const PUB_KEY: &str = "YOUR_PUBLIC_KEY";
 const APP_ID: u64 = 0;
 
 #[slash_command]
-async fn test(ctx: Context) -> Result<InteractionResponse, std::convert::Infallible> {
+async fn test(ctx: Context) -> Result<InteractionResponse, ResponseError> {
     // Send a followup message
     let fu = ctx
         .clone()
@@ -19,7 +20,7 @@ async fn test(ctx: Context) -> Result<InteractionResponse, std::convert::Infalli
         .await;
 
     // Mind you: The return value is the INITIAL RESPONSE. What is returned here is sent directly to Discord
-    return ctx.respond().message("I was summoned?").finish();
+    return ctx.respond().message("I was summoned?").build();
 }
 
 // The lib uses actix-web
@@ -3,6 +3,7 @@ extern crate rusty_interaction;
 
 use rusty_interaction::handler::InteractionHandler;
 use rusty_interaction::types::interaction::*;
+use rusty_interaction::Builder;
 
 use async_std::task;
 use std::time::Duration;
@@ -14,7 +15,7 @@ const PUB_KEY: &str = "YOUR_PUBLIC_KEY";
 const APP_ID: u64 = 0;
 
 #[slash_command]
-async fn test(ctx: Context) -> Result<InteractionResponse, std::convert::Infallible> {
+async fn test(ctx: Context) -> Result<InteractionResponse, ResponseError> {
     let m = ctx.clone();
     // Spawn a new thread before sending a response.
     Arbiter::spawn(async move {
@@ -24,7 +25,7 @@ async fn test(ctx: Context) -> Result<InteractionResponse, std::convert::Infalli
         m.delete_original().await;
     });
 
-    return ctx.respond().message("I was summoned?").finish();
+    return ctx.respond().message("I was summoned?").build();
 }
 
 // The lib uses actix-web
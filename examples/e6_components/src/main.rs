@@ -1,8 +1,6 @@
 #[macro_use]
 extern crate rusty_interaction;
 
-use std::convert::Infallible;
-
 use rusty_interaction::handler::InteractionHandler;
 use rusty_interaction::types::interaction::*;
 // Import for using components
@@ -19,21 +17,21 @@ const APP_ID: u64 = 0;
 
 // Use the component_handler macro.
 #[component_handler]
-async fn edit_button(ctx: Context) -> Result<InteractionResponse, Infallible> {
-    return ctx.respond().message("HAHA").finish();
+async fn edit_button(ctx: Context) -> Result<InteractionResponse, ResponseError> {
+    return ctx.respond().message("HAHA").build();
 }
 
 // We defer in this instance, because we don't want to edit anything
 #[component_handler]
 #[defer]
-async fn delete_button(ctx: Context) -> Result<InteractionResponse, Infallible> {
+async fn delete_button(ctx: Context) -> Result<InteractionResponse, ResponseError> {
     ctx.delete_original().await;
 
     // Since we've deleted the original message, it's safe to use respond().none()
-    return ctx.respond().none();
+    return Ok(ctx.respond().none());
 }
 #[slash_command]
-async fn test(ctx: Context) -> Result<InteractionResponse, Infallible> {
+async fn test(ctx: Context) -> Result<InteractionResponse, ResponseError> {
     // Let's build our message!
     let resp = ctx
         .respond()
@@ -88,7 +86,7 @@ async fn test(ctx: Context) -> Result<InteractionResponse, Infallible> {
                 .build()
                 .unwrap(),
         )
-        .finish();
+        .build();
 
     return resp;
 }
@@ -1,32 +1,31 @@
 #[macro_use]
 extern crate rusty_interaction;
 
-use std::convert::Infallible;
-
-use rusty_interaction::handler::InteractionHandler;
+use rusty_interaction::handler::{HandlerError, InteractionHandler};
 use rusty_interaction::types::components::*;
 use rusty_interaction::types::interaction::*;
 // Embed objects can be found here
 use rusty_interaction::types::embed::*;
+use rusty_interaction::Builder;
 
 const PUB_KEY: &str = "YOUR_PUBLIC_KEY";
 const APP_ID: u64 = 0;
 
 // Use the component_handler macro.
 #[component_handler]
-async fn edit_button(ctx: Context) -> Result<InteractionResponse, Infallible> {
-    return ctx.respond().message("HAHA").finish();
+async fn edit_button(ctx: Context) -> Result<InteractionResponse, ResponseError> {
+    return ctx.respond().message("HAHA").build();
 }
 
 // We defer in this instance, because we don't want to edit anything
 #[component_handler]
 #[defer]
-async fn delete_button(ctx: Context) -> Result<InteractionResponse, Infallible> {
+async fn delete_button(ctx: Context) -> Result<InteractionResponse, ResponseError> {
     if let Ok(_) = ctx.delete_original().await {}
-    return ctx.respond().none();
+    return Ok(ctx.respond().none());
 }
 #[slash_command]
-async fn test(ctx: Context) -> Result<InteractionResponse, Infallible> {
+async fn test(ctx: Context) -> Result<InteractionResponse, HandlerError> {
     // You can use the EmbedBuilder to build embeds
     // ...you might have figured that out when looking at the name.
     let embed = EmbedBuilder::default()
@@ -39,7 +38,7 @@ async fn test(ctx: Context) -> Result<InteractionResponse, Infallible> {
                 .value("Right?"),
         )
         .footer(EmbedFooter::default().text("rusty-interaction"))
-        .finish();
+        .build()?;
 
     let components = ComponentRowBuilder::default()
         .add_button(
@@ -47,9 +46,9 @@ async fn test(ctx: Context) -> Result<InteractionResponse, Infallible> {
                 .label("Delete")
                 .custom_id("DELETE")
                 .style(&ComponentButtonStyle::Danger)
-                .finish(),
+                .build()?,
         )
-        .finish();
+        .build()?;
 
     // Let's build our message!
     let resp = ctx
@@ -59,9 +58,9 @@ async fn test(ctx: Context) -> Result<InteractionResponse, Infallible> {
         .add_component_row(components)
         // Add the embed. You can add a maximum of 10 embeds
         .add_embed(&embed)
-        .finish();
+        .build()?;
 
-    return resp;
+    return Ok(resp);
 }
 
 // The lib uses actix-web
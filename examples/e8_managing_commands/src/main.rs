@@ -1,9 +1,8 @@
 #[macro_use]
 extern crate rusty_interaction;
 
-use std::convert::Infallible;
-
 use rusty_interaction::handler::{InteractionHandler, ManipulationScope};
+use rusty_interaction::types::id::GuildId;
 use rusty_interaction::types::interaction::*;
 // Relevant imports here
 use rusty_interaction::types::application::{
@@ -16,72 +15,64 @@ const TOKEN: &str = "MY TOKEN";
 const APP_ID: u64 = 00000000000000000;
 
 #[slash_command]
+#[guild_only]
 async fn delete_self(
-    handler: &mut InteractionHandler,
+    handler: &InteractionHandler,
     ctx: Context,
-) -> Result<InteractionResponse, Infallible> {
-    let sec_ctx = ctx.clone();
-    if let Some(g) = sec_ctx.interaction.guild_id {
-        if let Some(data) = sec_ctx.interaction.data {
-            let cid = data.id;
+    guild: GuildId,
+) -> Result<InteractionResponse, ResponseError> {
+    if let Some(data) = &ctx.interaction.data {
+        let cid = data.id;
 
-            // Using this to remove the guild command
-            let r = handler
-                .deregister_guild_handle(g, cid.unwrap(), &ManipulationScope::All)
-                .await;
-            if r.is_ok() {
-                return ctx.respond().content("`/generated` deleted!").finish();
-            } else {
-                return ctx.respond().content("Something went wrong!").finish();
-            }
+        // Using this to remove the guild command
+        let r = handler
+            .deregister_guild_handle(guild, cid.unwrap(), &ManipulationScope::All)
+            .await;
+        if r.is_ok() {
+            return ctx.respond().content("`/generated` deleted!").build();
         }
-        return ctx.respond().content("Something went wrong!").finish();
-    } else {
-        return ctx
-            .respond()
-            .content("This command should be invoked in a guild!")
-            .finish();
     }
+    return ctx.respond().content("Something went wrong!").build();
 }
 
 #[slash_command]
+// Only members that can manage the server may register commands in it
+#[guild_only]
+#[require_permissions(MANAGE_GUILD)]
 async fn test(
-    handler: &mut InteractionHandler,
+    handler: &InteractionHandler,
     ctx: Context,
-) -> Result<InteractionResponse, Infallible> {
-    if let Some(i) = ctx.interaction.guild_id {
-        // Build a simple command
-        let cmd = SlashCommandDefinitionBuilder::default()
-            .name("generated")
-            .description("This is a generated guild command!")
-            .add_option(
-                ApplicationCommandOption::default()
-                    .option_type(&ApplicationCommandOptionType::String)
-                    .name("string")
-                    .description("I will do absolutely nothing with this"),
-            )
-            .build()
-            .unwrap();
+    guild: GuildId,
+) -> Result<InteractionResponse, ResponseError> {
+    // Build a simple command
+    let cmd = SlashCommandDefinitionBuilder::default()
+        .name("generated")
+        .description("This is a generated guild command!")
+        .add_option(
+            ApplicationCommandOption::default()
+                .option_type(&ApplicationCommandOptionType::String)
+                .name("string")
+                .description("I will do absolutely nothing with this"),
+        )
+        .build()
+        .unwrap();
 
-        match handler
-            .register_guild_handle(i, &cmd, delete_self, &ManipulationScope::All)
-            .await
-        {
-            Ok(_) => {
-                return ctx
-                    .respond()
-                    .content("`/generated` has been registered!")
-                    .finish();
-            }
-            Err(e) => {
-                return ctx
-                    .respond()
-                    .content(format!("Error ({}): \n```json\n{:?}```", e.code, e.message))
-                    .finish();
-            }
+    match handler
+        .register_guild_handle(guild, &cmd, delete_self, &ManipulationScope::All)
+        .await
+    {
+        Ok(_) => {
+            return ctx
+                .respond()
+                .content("`/generated` has been registered!")
+                .build();
+        }
+        Err(e) => {
+            return ctx
+                .respond()
+                .content(format!("Error ({}): \n```json\n{:?}```", e.code, e.message))
+                .build();
         }
-    } else {
-        return ctx.respond().content("Not in a guild!").finish();
     }
 }
 
@@ -1,52 +1,56 @@
 #[macro_use]
 extern crate rusty_interaction;
 
-use rusty_interaction::handler::{InteractionHandler, ManipulationScope};
-use rusty_interaction::types::interaction::*;
+use std::sync::Mutex;
+
 // Relevant imports here
-use rusty_interaction::types::application::{
-    ApplicationCommandOption, ApplicationCommandOptionType, SlashCommandDefinitionBuilder,
-};
+use rusty_interaction::extract::State;
+use rusty_interaction::handler::InteractionHandler;
+use rusty_interaction::types::interaction::*;
 use rusty_interaction::Builder;
 
 const PUB_KEY: &str = "YOUR PUB KEY";
 const APP_ID: u64 = 000000000000000000;
 
-// Must implement Clone
-#[derive(Clone)]
+// Shared between all handlers. Use interior mutability to change it
 struct MyStruct {
-    pub foo: u16,
+    pub foo: Mutex<u16>,
 }
 
 #[slash_command]
-async fn test(
-    handler: &mut InteractionHandler,
-    ctx: Context,
-) -> Result<InteractionResponse, std::convert::Infallible> {
-    // Get a mutable reference to MyStruct
-    let my_struct = handler.data.get_mut::<MyStruct>().unwrap();
-
-    my_struct.foo += 1;
-
-    return ctx
-        .respond()
-        .content(format!("Foo is {}", my_struct.foo))
-        .finish();
+async fn test(handler: &InteractionHandler, ctx: Context) -> Result<InteractionResponse, ResponseError> {
+    // Get the shared MyStruct
+    let my_struct = handler.state::<MyStruct>().unwrap();
+
+    let mut foo = my_struct.foo.lock().unwrap();
+    *foo += 1;
+
+    return ctx.respond().content(format!("Foo is {}", foo)).build();
+}
+
+#[slash_command]
+#[defer]
+// State can also be taken as a parameter. Deferred handlers see the same state
+async fn peek(ctx: Context, my_struct: State<MyStruct>) -> Result<InteractionResponse, ResponseError> {
+    let foo = *my_struct.foo.lock().unwrap();
+
+    return ctx.respond().content(format!("Foo is still {}", foo)).build();
 }
 
 // The lib uses actix-web
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
-    let my_struct = MyStruct { foo: 0 };
+    let my_struct = MyStruct { foo: Mutex::new(0) };
 
     env_logger::init_from_env(env_logger::Env::new().default_filter_or("info"));
 
     let mut handle = InteractionHandler::new(APP_ID, PUB_KEY, None);
 
     handle.add_global_command("summon", test);
+    handle.add_global_command("peek", peek);
 
-    // Add my_struct to the Data map
-    handle.add_data(my_struct);
+    // Add my_struct to the shared state
+    handle.add_state(my_struct);
 
     return handle.run(10080).await;
 }
@@ -0,0 +1,204 @@
+//! Collectors let a handler wait for the next component interaction on one of its messages.
+//!
+//! This keeps the state of a flow, like a confirmation or a quiz, in the handler instead of spreading it over
+//! static component handlers. A collected interaction goes through the middleware and cooldowns like any other,
+//! is acknowledged with a deferred message update, and its [`Context`] is returned to the waiting handler,
+//! which can then edit the message.
+//!
+//! The message must exist before waiting for it, so collectors are used in `#[defer]`red handlers:
+//!
+//! ```ignore
+//! #[slash_command]
+//! #[defer]
+//! async fn delete(handler: &InteractionHandler, ctx: Context) -> InteractionResponse {
+//!     let prompt = ctx.respond().content("Are you sure?").add_component_row(yes_no_buttons).build().unwrap();
+//!     ctx.edit_original(&WebhookMessage::from(prompt)).await.unwrap();
+//!
+//!     let answer = handler
+//!         .collect_component()
+//!         .original(&ctx)
+//!         .user(ctx.author_id.unwrap())
+//!         .timeout(Duration::from_secs(30))
+//!         .next()
+//!         .await;
+//!
+//!     match answer {
+//!         Some(click) if click.interaction.data.as_ref().unwrap().custom_id.as_deref() == Some("yes") => {
+//!             // ...
+//!         }
+//!         _ => ctx.respond().content("Cancelled").build().unwrap(),
+//!     }
+//! }
+//! ```
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use log::debug;
+use tokio::sync::oneshot;
+use tokio::time::timeout;
+
+use crate::types::id::{InteractionId, MessageId, UserId};
+use crate::types::interaction::{Context, InteractionType};
+
+// How long a collector waits when no timeout is set
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+// Which component interactions a collector accepts
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ComponentFilter {
+    pub(crate) message: Option<MessageId>,
+    pub(crate) original: Option<InteractionId>,
+    pub(crate) user: Option<UserId>,
+    pub(crate) custom_ids: Vec<String>,
+}
+
+impl ComponentFilter {
+    pub(crate) fn matches(&self, ctx: &Context) -> bool {
+        let i = &ctx.interaction;
+        let message = i.message.as_ref();
+
+        if let Some(id) = self.message {
+            if message.map(|m| m.id) != Some(id) {
+                return false;
+            }
+        }
+        if let Some(id) = self.original {
+            let original = message
+                .and_then(|m| m.interaction_metadata.as_ref())
+                .map(|m| m.id);
+            if original != Some(id) {
+                return false;
+            }
+        }
+        if self.user.is_some() && ctx.author_id != self.user {
+            return false;
+        }
+        if !self.custom_ids.is_empty() {
+            let custom_id = i.data.as_ref().and_then(|d| d.custom_id.as_deref());
+            if !custom_id.is_some_and(|c| self.custom_ids.iter().any(|id| id == c)) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+#[derive(Debug)]
+struct Waiter {
+    filter: ComponentFilter,
+    tx: oneshot::Sender<Context>,
+}
+
+// Collectors waiting for a component interaction. Shared by every clone of the handler
+#[derive(Clone, Debug, Default)]
+pub(crate) struct CollectorRegistry {
+    waiters: Arc<Mutex<Vec<Waiter>>>,
+}
+
+impl CollectorRegistry {
+    fn register(&self, filter: ComponentFilter) -> oneshot::Receiver<Context> {
+        let (tx, rx) = oneshot::channel();
+        let mut waiters = self.waiters.lock().unwrap();
+
+        // Collectors that timed out are removed here
+        waiters.retain(|w| !w.tx.is_closed());
+        waiters.push(Waiter { filter, tx });
+        rx
+    }
+
+    /// Whether a collector is waiting for `ctx`
+    pub(crate) fn is_waiting(&self, ctx: &Context) -> bool {
+        ctx.interaction.r#type == InteractionType::MessageComponent
+            && self
+                .waiters
+                .lock()
+                .unwrap()
+                .iter()
+                .any(|w| !w.tx.is_closed() && w.filter.matches(ctx))
+    }
+
+    /// Hands `ctx` to the first collector that accepts it. Gives `ctx` back if none did
+    pub(crate) fn deliver(&self, ctx: Context) -> Option<Context> {
+        if ctx.interaction.r#type != InteractionType::MessageComponent {
+            return Some(ctx);
+        }
+
+        let mut waiters = self.waiters.lock().unwrap();
+        waiters.retain(|w| !w.tx.is_closed());
+
+        let mut ctx = ctx;
+        while let Some(pos) = waiters.iter().position(|w| w.filter.matches(&ctx)) {
+            let waiter = waiters.remove(pos);
+            match waiter.tx.send(ctx) {
+                Ok(()) => return None,
+                // The collector stopped waiting in the meantime, try the next one
+                Err(c) => ctx = c,
+            }
+        }
+        Some(ctx)
+    }
+}
+
+#[derive(Debug)]
+/// Waits for the next component interaction that matches all of its filters.
+///
+/// Created with [`InteractionHandler::collect_component`](crate::handler::InteractionHandler::collect_component).
+#[must_use = "a collector does nothing until `next` is awaited"]
+pub struct ComponentCollector {
+    registry: CollectorRegistry,
+    filter: ComponentFilter,
+    timeout: Duration,
+}
+
+impl ComponentCollector {
+    pub(crate) fn new(registry: CollectorRegistry) -> Self {
+        Self {
+            registry,
+            filter: ComponentFilter::default(),
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// Only accept components on this message
+    pub fn message(mut self, id: impl Into<MessageId>) -> Self {
+        self.filter.message = Some(id.into());
+        self
+    }
+
+    /// Only accept components on messages sent in response to `ctx`, like its original response and follow-ups
+    pub fn original(mut self, ctx: &Context) -> Self {
+        self.filter.original = ctx.interaction.id;
+        self
+    }
+
+    /// Only accept components used by this user
+    pub fn user(mut self, id: impl Into<UserId>) -> Self {
+        self.filter.user = Some(id.into());
+        self
+    }
+
+    /// Only accept components with this custom ID. Can be called multiple times to accept any of them
+    pub fn custom_id(mut self, custom_id: impl Into<String>) -> Self {
+        self.filter.custom_ids.push(custom_id.into());
+        self
+    }
+
+    /// How long to wait. Defaults to 60 seconds
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Wait for the next matching component interaction. Returns `None` on timeout
+    pub async fn next(self) -> Option<Context> {
+        let rx = self.registry.register(self.filter);
+
+        match timeout(self.timeout, rx).await {
+            Ok(Ok(ctx)) => Some(ctx),
+            _ => {
+                debug!("Component collector timed out");
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,144 @@
+//! Rate limit how often commands and components can be used.
+//!
+//! A [`Cooldown`] can be set for a command or component with [`InteractionHandler::set_cooldown`](crate::handler::InteractionHandler::set_cooldown),
+//! or declared on the handler itself with the `#[cooldown]` attribute:
+//!
+//! ```ignore
+//! fn is_admin(ctx: &Context) -> bool {
+//!     // ...
+//! }
+//!
+//! #[slash_command]
+//! // Once every 30 seconds per user, admins are exempt
+//! #[cooldown(30, user, exempt = "is_admin")]
+//! async fn expensive(ctx: Context) -> InteractionResponse {
+//!     // ...
+//! }
+//! ```
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::types::interaction::{Context, InteractionResponse};
+
+// Expired entries are cleaned up once a cooldown tracks this many buckets
+pub(crate) const CLEANUP_THRESHOLD: usize = 1024;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+/// What a [`Cooldown`] applies to
+pub enum CooldownBucket {
+    /// Every user has their own cooldown
+    User,
+    /// Every guild has its own cooldown. In DMs, every user has their own cooldown
+    Guild,
+    /// Every channel has its own cooldown
+    Channel,
+    /// One cooldown shared by everyone
+    Global,
+}
+
+impl CooldownBucket {
+    fn key(&self, ctx: &Context) -> u64 {
+        let i = &ctx.interaction;
+        let user = ctx.author_id.map_or(0, |u| u.get());
+
+        match self {
+            CooldownBucket::User => user,
+            CooldownBucket::Guild => i.guild_id.map_or(user, |g| g.get()),
+            CooldownBucket::Channel => i.channel_id.map_or(user, |c| c.get()),
+            CooldownBucket::Global => 0,
+        }
+    }
+}
+
+// The default cooldown message
+fn default_message(remaining: Duration) -> String {
+    format!(
+        "You're doing that too often. Try again in {}s.",
+        remaining.as_secs_f64().ceil()
+    )
+}
+
+#[derive(Clone)]
+/// Limits the use of a command or component to once per duration.
+///
+/// Clones share their state, so a cloned cooldown is the same cooldown.
+pub struct Cooldown {
+    duration: Duration,
+    bucket: CooldownBucket,
+    exempt: Option<fn(&Context) -> bool>,
+    message: fn(Duration) -> String,
+    last_used: Arc<Mutex<HashMap<u64, Instant>>>,
+}
+
+impl std::fmt::Debug for Cooldown {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Cooldown")
+            .field("duration", &self.duration)
+            .field("bucket", &self.bucket)
+            .finish()
+    }
+}
+
+impl Cooldown {
+    /// Creates a new cooldown of `duration` per `bucket`
+    pub fn new(duration: Duration, bucket: CooldownBucket) -> Self {
+        Self {
+            duration,
+            bucket,
+            exempt: None,
+            message: default_message,
+            last_used: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Interactions for which `f` returns `true` are not limited, and don't start a cooldown
+    pub fn exempt(mut self, f: fn(&Context) -> bool) -> Self {
+        self.exempt = Some(f);
+        self
+    }
+
+    /// Sets the message shown to users on cooldown. `f` gets the time left before they can try again
+    pub fn message(mut self, f: fn(Duration) -> String) -> Self {
+        self.message = f;
+        self
+    }
+
+    /// Records a use. Returns the time left if the bucket of this interaction is on cooldown
+    pub fn check(&self, ctx: &Context) -> Result<(), Duration> {
+        if self.exempt.is_some_and(|f| f(ctx)) {
+            return Ok(());
+        }
+
+        let key = self.bucket.key(ctx);
+        let now = Instant::now();
+        let mut last_used = self.last_used.lock().unwrap();
+
+        if let Some(last) = last_used.get(&key) {
+            let elapsed = now.duration_since(*last);
+            if elapsed < self.duration {
+                return Err(self.duration - elapsed);
+            }
+        }
+
+        if last_used.len() >= CLEANUP_THRESHOLD {
+            let duration = self.duration;
+            last_used.retain(|_, last| now.duration_since(*last) < duration);
+        }
+        last_used.insert(key, now);
+        Ok(())
+    }
+
+    /// Like [`Cooldown::check`], but returns the ephemeral reply to send if on cooldown
+    pub fn check_response(&self, ctx: &Context) -> Option<InteractionResponse> {
+        let remaining = self.check(ctx).err()?;
+
+        Some(ctx.ephemeral_reply((self.message)(remaining)))
+    }
+
+    // The number of buckets that have a recorded use
+    #[cfg(test)]
+    pub(crate) fn tracked(&self) -> usize {
+        self.last_used.lock().unwrap().len()
+    }
+}
@@ -0,0 +1,214 @@
+//! Extractors let handlers declare what they need from an interaction as parameters.
+//!
+//! Every parameter of a `#[slash_command]` or `#[component_handler]` function that is taken by value
+//! must implement [`FromContext`]. The parameters are extracted before the body runs.
+//! If an extractor fails, the handler is not called and the user gets an ephemeral reply describing the error.
+//!
+//! ```ignore
+//! #[derive(Deserialize)]
+//! struct BanArgs {
+//!     user: UserId,
+//!     reason: Option<String>,
+//! }
+//!
+//! #[slash_command]
+//! async fn ban(ctx: Context, guild: GuildId, Options(args): Options<BanArgs>, State(db): State<Db>) -> InteractionResponse {
+//!     // ...
+//! }
+//! ```
+use std::error;
+use std::fmt::{self, Display};
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+
+use log::debug;
+use serde::de::DeserializeOwned;
+
+use crate::handler::InteractionHandler;
+use crate::types::id::{ChannelId, GuildId};
+use crate::types::interaction::{Context, InteractionResponse};
+use crate::types::user::{Member, User};
+
+/// A type that can be extracted from an incoming interaction
+pub trait FromContext: Sized {
+    /// Extract `Self` from the interaction
+    fn from_context(handler: &InteractionHandler, ctx: &Context) -> Result<Self, ExtractError>;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// An error that occurred when extracting a handler parameter.
+///
+/// The [`Display`] output is shown to the user that invoked the interaction.
+pub enum ExtractError {
+    /// The interaction was not invoked in a guild, but the handler requires one
+    NotInGuild,
+    /// The interaction is missing this field
+    MissingField(&'static str),
+    /// The command options could not be deserialized
+    InvalidOptions(String),
+    /// No state of this type was added with [`InteractionHandler::add_state`]
+    MissingState(&'static str),
+}
+
+impl Display for ExtractError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExtractError::NotInGuild => write!(f, "This can only be used in a server."),
+            ExtractError::MissingField(field) => {
+                write!(f, "The interaction is missing its {}.", field)
+            }
+            ExtractError::InvalidOptions(e) => write!(f, "Invalid options: {}", e),
+            ExtractError::MissingState(t) => {
+                write!(f, "Something went wrong: `{}` is not available.", t)
+            }
+        }
+    }
+}
+
+impl error::Error for ExtractError {}
+
+// Used by the attribute macros to extract a parameter, or build the error reply
+#[doc(hidden)]
+pub fn __extract<T: FromContext>(
+    handler: &InteractionHandler,
+    ctx: &Context,
+) -> Result<T, Box<InteractionResponse>> {
+    T::from_context(handler, ctx).map_err(|e| {
+        debug!("Failed to extract {}: {:?}", std::any::type_name::<T>(), e);
+
+        Box::new(ctx.ephemeral_reply(e))
+    })
+}
+
+impl FromContext for Context {
+    fn from_context(_: &InteractionHandler, ctx: &Context) -> Result<Self, ExtractError> {
+        Ok(ctx.clone())
+    }
+}
+
+/// Makes a parameter optional: `None` if extracting `T` failed
+impl<T: FromContext> FromContext for Option<T> {
+    fn from_context(handler: &InteractionHandler, ctx: &Context) -> Result<Self, ExtractError> {
+        Ok(T::from_context(handler, ctx).ok())
+    }
+}
+
+/// The user that invoked the interaction
+impl FromContext for User {
+    fn from_context(_: &InteractionHandler, ctx: &Context) -> Result<Self, ExtractError> {
+        let i = &ctx.interaction;
+        i.member
+            .as_ref()
+            .map(|m| m.user.clone())
+            .or_else(|| i.user.clone())
+            .ok_or(ExtractError::MissingField("user"))
+    }
+}
+
+/// The member that invoked the interaction. Fails outside of guilds
+impl FromContext for Member {
+    fn from_context(_: &InteractionHandler, ctx: &Context) -> Result<Self, ExtractError> {
+        ctx.interaction
+            .member
+            .clone()
+            .ok_or(ExtractError::NotInGuild)
+    }
+}
+
+/// The guild the interaction was invoked in. Fails outside of guilds
+impl FromContext for GuildId {
+    fn from_context(_: &InteractionHandler, ctx: &Context) -> Result<Self, ExtractError> {
+        ctx.interaction.guild_id.ok_or(ExtractError::NotInGuild)
+    }
+}
+
+/// The channel the interaction was invoked in
+impl FromContext for ChannelId {
+    fn from_context(_: &InteractionHandler, ctx: &Context) -> Result<Self, ExtractError> {
+        ctx.interaction
+            .channel_id
+            .ok_or(ExtractError::MissingField("channel"))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// The locale of the user that invoked the interaction (Ex. `en-US`), falling back to the guild's locale
+pub struct Locale(pub String);
+
+impl FromContext for Locale {
+    fn from_context(_: &InteractionHandler, ctx: &Context) -> Result<Self, ExtractError> {
+        let i = &ctx.interaction;
+        i.locale
+            .clone()
+            .or_else(|| i.guild_locale.clone())
+            .map(Locale)
+            .ok_or(ExtractError::MissingField("locale"))
+    }
+}
+
+impl Deref for Locale {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+#[derive(Debug)]
+/// State added with [`InteractionHandler::add_state`]. Every handler gets the same instance
+pub struct State<T>(pub Arc<T>);
+
+impl<T> Clone for State<T> {
+    fn clone(&self) -> Self {
+        State(self.0.clone())
+    }
+}
+
+impl<T: Send + Sync + 'static> FromContext for State<T> {
+    fn from_context(handler: &InteractionHandler, _: &Context) -> Result<Self, ExtractError> {
+        handler
+            .state::<T>()
+            .map(State)
+            .ok_or(ExtractError::MissingState(std::any::type_name::<T>()))
+    }
+}
+
+impl<T> Deref for State<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+#[derive(Clone, Debug)]
+/// The command options, deserialized into `T`.
+///
+/// See [`ApplicationCommandInteractionData::deserialize_options`](crate::types::application::ApplicationCommandInteractionData::deserialize_options) for how options are mapped.
+pub struct Options<T>(pub T);
+
+impl<T: DeserializeOwned> FromContext for Options<T> {
+    fn from_context(_: &InteractionHandler, ctx: &Context) -> Result<Self, ExtractError> {
+        ctx.interaction
+            .data
+            .as_ref()
+            .ok_or(ExtractError::MissingField("data"))?
+            .deserialize_options()
+            .map(Options)
+            .map_err(|e| ExtractError::InvalidOptions(e.to_string()))
+    }
+}
+
+impl<T> Deref for Options<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Options<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
@@ -0,0 +1,128 @@
+//! Guards check who can use a handler, and where, before its body runs.
+//!
+//! They are usually declared with attributes next to `#[slash_command]` or `#[component_handler]`:
+//!
+//! ```ignore
+//! #[slash_command]
+//! #[guild_only]
+//! #[require_permissions(MANAGE_GUILD)]
+//! async fn settings(ctx: Context) -> InteractionResponse {
+//!     // ...
+//! }
+//! ```
+//!
+//! A rejected interaction gets an ephemeral reply describing the [`GuardError`].
+//! The checks can also be called directly, like `guild_only(&ctx)?`.
+use std::error;
+use std::fmt::{self, Display};
+
+use log::debug;
+
+use crate::handler::InteractionHandler;
+use crate::types::guild::Permissions;
+use crate::types::id::RoleId;
+use crate::types::interaction::{Context, InteractionResponse};
+
+#[derive(Debug, Clone, PartialEq)]
+/// Why a guard rejected an interaction.
+///
+/// The [`Display`] output is shown to the user that invoked the interaction.
+pub enum GuardError {
+    /// The interaction was not invoked in a guild
+    GuildOnly,
+    /// The interaction was invoked in a guild
+    DmOnly,
+    /// The user is not an owner added with [`InteractionHandler::add_owner`]
+    OwnerOnly,
+    /// The member does not have all of the required roles
+    MissingRoles,
+    /// The member is missing these permissions
+    MissingPermissions(Permissions),
+}
+
+impl Display for GuardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GuardError::GuildOnly => write!(f, "This can only be used in a server."),
+            GuardError::DmOnly => write!(f, "This can only be used in DMs."),
+            GuardError::OwnerOnly => write!(f, "This can only be used by the bot owner."),
+            GuardError::MissingRoles => {
+                write!(f, "You don't have the roles required to use this.")
+            }
+            GuardError::MissingPermissions(p) => {
+                let names: Vec<_> = p.iter_names().map(|(name, _)| name).collect();
+                write!(
+                    f,
+                    "You need the following permissions to use this: {}",
+                    names.join(", ")
+                )
+            }
+        }
+    }
+}
+
+impl error::Error for GuardError {}
+
+/// Only allow interactions invoked in a guild
+pub fn guild_only(ctx: &Context) -> Result<(), GuardError> {
+    match ctx.interaction.guild_id {
+        Some(_) => Ok(()),
+        None => Err(GuardError::GuildOnly),
+    }
+}
+
+/// Only allow interactions invoked outside of guilds
+pub fn dm_only(ctx: &Context) -> Result<(), GuardError> {
+    match ctx.interaction.guild_id {
+        Some(_) => Err(GuardError::DmOnly),
+        None => Ok(()),
+    }
+}
+
+/// Only allow users added with [`InteractionHandler::add_owner`]
+pub fn owner_only(handler: &InteractionHandler, ctx: &Context) -> Result<(), GuardError> {
+    match ctx.author_id {
+        Some(user) if handler.is_owner(user) => Ok(()),
+        _ => Err(GuardError::OwnerOnly),
+    }
+}
+
+/// Only allow members that have all of `roles`. Fails outside of guilds
+pub fn require_roles(ctx: &Context, roles: &[RoleId]) -> Result<(), GuardError> {
+    let member = ctx
+        .interaction
+        .member
+        .as_ref()
+        .ok_or(GuardError::GuildOnly)?;
+
+    if roles.iter().all(|r| member.roles.contains(r)) {
+        Ok(())
+    } else {
+        Err(GuardError::MissingRoles)
+    }
+}
+
+/// Only allow members that have all of `permissions` in the channel. Administrators are always allowed. Fails outside of guilds
+pub fn require_permissions(ctx: &Context, permissions: Permissions) -> Result<(), GuardError> {
+    let member = ctx
+        .interaction
+        .member
+        .as_ref()
+        .ok_or(GuardError::GuildOnly)?;
+    let has = member.permission_set().unwrap_or_default();
+
+    if has.contains(Permissions::ADMINISTRATOR) || has.contains(permissions) {
+        Ok(())
+    } else {
+        Err(GuardError::MissingPermissions(permissions - has))
+    }
+}
+
+// Used by the attribute macros to build the reply to a rejected interaction
+#[doc(hidden)]
+pub fn __guard(result: Result<(), GuardError>, ctx: &Context) -> Option<InteractionResponse> {
+    let e = result.err()?;
+    debug!("Guard rejected interaction: {:?}", e);
+
+    Some(ctx.ephemeral_reply(e))
+}
@@ -3,21 +3,32 @@ use crate::security::*;
 #[cfg(feature = "extended-handler")]
 use crate::types::application::*;
 
-#[cfg(feature = "handler")]
+#[cfg(feature = "handler-core")]
 use crate::types::interaction::*;
 
+#[cfg(feature = "extended-handler")]
+use crate::types::monetization::*;
 #[cfg(feature = "extended-handler")]
 use crate::types::HttpError;
-use crate::types::Snowflake;
+use crate::middleware::Middleware;
+use crate::cooldown::Cooldown;
+use crate::collector::{CollectorRegistry, ComponentCollector};
+#[cfg(feature = "handler")]
+use crate::server::InteractionServerConfig;
+#[cfg(feature = "extended-handler")]
+use crate::types::id::{EntitlementId, GuildId};
+use crate::types::id::{ApplicationId, CommandId, UserId};
 #[cfg(feature = "extended-handler")]
 use crate::{
     expect_specific_api_response, expect_successful_api_response,
     expect_successful_api_response_and_return,
 };
-use actix_web::http::StatusCode;
-use actix_web::{web, App, HttpRequest, HttpResponse, HttpServer, Result};
+#[cfg(feature = "handler")]
+use actix_web::{HttpRequest, HttpResponse};
 use reqwest::header;
 use reqwest::Client;
+#[cfg(feature = "extended-handler")]
+use reqwest::StatusCode;
 
 use log::{debug, error};
 use std::fmt;
@@ -26,24 +37,155 @@ use anymap::{CloneAny, Map};
 
 use ed25519_dalek::{PUBLIC_KEY_LENGTH, VerifyingKey};
 
-use std::{collections::HashMap, future::Future, pin::Pin, sync::Mutex};
+use std::any::{Any, TypeId};
+use std::{collections::HashMap, future::Future, pin::Pin, sync::{Arc, Mutex}};
 use std::convert::TryInto;
+use std::time::Duration;
+use tokio::sync::oneshot;
+use tokio::time::timeout;
 use hex::FromHex;
+#[cfg(feature = "handler")]
 use rustls::ServerConfig;
 
 type AnyMap = Map<dyn CloneAny + Send + Sync>;
 
+type StateMap = HashMap<TypeId, Arc<dyn Any + Send + Sync>>;
+
 type HandlerFunction = fn(
-    &mut InteractionHandler,
+    &InteractionHandler,
     Context,
 ) -> Pin<Box<dyn Future<Output = InteractionResponse> + Send + '_>>;
 
+/// An error returned by a handler
+pub type HandlerError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Maps an error returned by a handler to the reply sent to the user. Set with [`InteractionHandler::on_error`]
+pub type ErrorHandlerFunction = fn(&Context, &HandlerError) -> InteractionResponse;
+
+/// A value a handler can return: an [`InteractionResponse`], or a `Result` of one with any error type
+pub trait IntoHandlerResult {
+    /// Convert into a `Result`
+    fn into_handler_result(self) -> Result<InteractionResponse, HandlerError>;
+}
+
+impl IntoHandlerResult for InteractionResponse {
+    fn into_handler_result(self) -> Result<InteractionResponse, HandlerError> {
+        Ok(self)
+    }
+}
+
+impl<E: Into<HandlerError>> IntoHandlerResult for Result<InteractionResponse, E> {
+    fn into_handler_result(self) -> Result<InteractionResponse, HandlerError> {
+        self.map_err(Into::into)
+    }
+}
+
+// The default error hook: tells the user something went wrong, without exposing the error
+fn default_error_reply(ctx: &Context, _: &HandlerError) -> InteractionResponse {
+    ctx.ephemeral_reply("Something went wrong while handling this interaction.")
+}
+
+// Used by the attribute macros to turn what a handler returned into a response
+#[doc(hidden)]
+pub fn __into_response(
+    result: impl IntoHandlerResult,
+    on_error: ErrorHandlerFunction,
+    ctx: &Context,
+) -> InteractionResponse {
+    result.into_handler_result().unwrap_or_else(|e| {
+        error!("Handler returned an error: {}", e);
+        on_error(ctx, &e)
+    })
+}
+
+// Used by the attribute macros to run deferred handlers. Works on any tokio runtime, including the one of actix-web
+#[doc(hidden)]
+pub fn __spawn(future: impl Future<Output = ()> + Send + 'static) {
+    tokio::spawn(future);
+}
+
+// How a response that is ready after the handler was auto-deferred is delivered
+#[derive(Debug, PartialEq)]
+pub(crate) enum LateDelivery {
+    /// Replaces the deferred response
+    EditOriginal,
+    /// Sent as a new message
+    Followup,
+}
+
+// Only a response of the kind that was deferred can replace it. Editing the original response of a component
+// interaction that was deferred with an update edits the component's message, which a new message must not replace
+pub(crate) fn late_delivery(
+    deferred: &InteractionResponseType,
+    response: &InteractionResponseType,
+) -> Option<LateDelivery> {
+    use InteractionResponseType as T;
+
+    match (deferred, response) {
+        (T::DefferedChannelMessageWithSource, T::ChannelMessageWithSource)
+        | (T::DefferedUpdateMessage, T::UpdateMessage) => Some(LateDelivery::EditOriginal),
+        (_, T::ChannelMessageWithSource) | (_, T::UpdateMessage) => Some(LateDelivery::Followup),
+        _ => None,
+    }
+}
+
+// Delivers a response that is ready after the handler was auto-deferred
+pub(crate) async fn deliver_late(
+    ctx: &Context,
+    deferred: &InteractionResponseType,
+    response: InteractionResponse,
+) {
+    match late_delivery(deferred, &response.r#type) {
+        // There is nothing to edit the deferred response with, or to send as a followup
+        Some(_) if response.data.is_none() => error!(
+            "Handler returned a {:?} response without data after deferring, dropping it",
+            response.r#type
+        ),
+        Some(LateDelivery::EditOriginal) => {
+            if let Err(e) = ctx.edit_original(&WebhookMessage::from(response)).await {
+                error!("Editing original message failed: {:?}", e);
+            }
+        }
+        Some(LateDelivery::Followup) => {
+            if let Err(e) = ctx.create_followup(&response.into_followup()).await {
+                error!("Sending followup message failed: {:?}", e);
+            }
+        }
+        None => debug!("Can't deliver a {:?} response after deferring, dropping it", response.r#type),
+    }
+}
+
+// Routes a component interaction to the collector waiting for it, so middleware and cooldowns run like for handlers.
+// If the collector stopped waiting in the meantime, the component falls through to its static route
+fn collected(
+    handler: &InteractionHandler,
+    ctx: Context,
+) -> Pin<Box<dyn Future<Output = InteractionResponse> + Send + '_>> {
+    Box::pin(async move {
+        // Collectors have no guards, so their cooldown is checked here. Handlers check it after their guards
+        if let Some(r) = handler.__cooldown(&ctx) {
+            return r;
+        }
+        let ctx = match handler.collectors.deliver(ctx) {
+            None => return InteractionResponse::new(InteractionResponseType::DefferedUpdateMessage, None),
+            Some(ctx) => ctx,
+        };
+
+        debug!("Component collector stopped waiting, falling through to the component handler");
+        match handler.component_handle(&ctx) {
+            Some(h) => h(handler, ctx).await,
+            // `process` answers this like an unknown route
+            None => InteractionResponse::new(InteractionResponseType::None, None),
+        }
+    })
+}
+
 macro_rules! match_handler_response {
     ($response:ident) => {
 
         match $response.r#type {
                 InteractionResponseType::None => {
-                    Ok(HttpResponse::build(StatusCode::NO_CONTENT).finish())
+                    (204, String::new())
                 }
                 InteractionResponseType::DefferedChannelMessageWithSource
                 | InteractionResponseType::DefferedUpdateMessage => {
@@ -51,20 +193,18 @@ macro_rules! match_handler_response {
                     If an application is first sending a deffered channel message response, this usually means the system
                     is still processing whatever it is doing.
                     See the spec: https://tools.ietf.org/html/rfc7231#section-6.3.3 */
-                    Ok(HttpResponse::build(StatusCode::ACCEPTED).json($response))
+                    (202, serde_json::to_string(&$response).unwrap_or_default())
                 }
                 _ => {
                     // Send out a response to Discord
-                    let r = HttpResponse::build(StatusCode::OK).json($response);
-
-                    Ok(r)
+                    (200, serde_json::to_string(&$response).unwrap_or_default())
                 }
             }
 
     };
 }
 
-#[cfg(feature = "handler")]
+#[cfg(feature = "handler-core")]
 #[non_exhaustive]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 /// Used for some functions to define which scope should be manipulated.
@@ -77,28 +217,49 @@ pub enum ManipulationScope {
     Discord,
 }
 
-#[cfg(feature = "handler")]
+#[cfg(feature = "handler-core")]
 #[derive(Clone)]
 /// The InteractionHandler is the 'thing' that will handle your incoming interactions.
-/// It does interaction validation (as required by Discord) and provides a pre-defined actix-web server
-/// with [`InteractionHandler::run`] and [`InteractionHandler::run_ssl`]
+/// It does interaction validation (as required by Discord) and routes them with [`InteractionHandler::process`].
+/// With the `handler` feature, it also provides a pre-defined actix-web server
+/// with `InteractionHandler::run` and `InteractionHandler::run_ssl`
 pub struct InteractionHandler {
-    application_id: Snowflake,
+    application_id: ApplicationId,
 
     app_public_key: VerifyingKey,
     client: Client,
 
-    global_handles: HashMap<&'static str, HandlerFunction>,
-    component_handles: HashMap<&'static str, HandlerFunction>,
+    // Deferred handlers run on a clone of the handler. Everything that isn't cheap to copy is behind an `Arc`,
+    // so the clone shares it
+    global_handles: Arc<HashMap<&'static str, HandlerFunction>>,
+    component_handles: Arc<HashMap<&'static str, HandlerFunction>>,
+    modal_handles: Arc<HashMap<&'static str, HandlerFunction>>,
 
     // These handles are 'forgotten' every time the app is shutdown (whatever the reason may be).
-    guild_handles: HashMap<Snowflake, HandlerFunction>,
+    // Handlers can register them, so they are behind a lock
+    guild_handles: Arc<Mutex<HashMap<CommandId, HandlerFunction>>>,
+
+    default_allowed_mentions: Option<AllowedMentions>,
+
+    error_handler: ErrorHandlerFunction,
+
+    auto_defer: Option<Duration>,
+
+    middleware: Arc<Vec<Arc<dyn Middleware>>>,
+
+    cooldowns: Arc<HashMap<&'static str, Cooldown>>,
+
+    owners: Vec<UserId>,
+
+    state: Arc<StateMap>,
+
+    collectors: CollectorRegistry,
 
     /// Field to access data
-    pub data: AnyMap,
+    pub data: Arc<AnyMap>,
 }
 
-#[cfg(feature = "handler")]
+#[cfg(feature = "handler-core")]
 // Only here to make Debug less generic, so I can send a reference
 impl fmt::Debug for InteractionHandler {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -107,18 +268,21 @@ impl fmt::Debug for InteractionHandler {
             .field("app_public_key", &self.app_public_key)
             .field("global_handles_len", &self.global_handles.len())
             .field("component_handles_len", &self.component_handles.len())
+            .field("modal_handles_len", &self.modal_handles.len())
+            .field("middleware_len", &self.middleware.len())
             .finish();
     }
 }
 
-#[cfg(feature = "handler")]
+#[cfg(feature = "handler-core")]
 impl InteractionHandler {
     /// Initalizes a new `InteractionHandler`
     pub fn new(
-        app_id: Snowflake,
+        app_id: impl Into<ApplicationId>,
         pbk_str: impl AsRef<str>,
         token: Option<&String>,
     ) -> InteractionHandler {
+        let app_id = app_id.into();
         let bytes: Vec<u8> = FromHex::from_hex(pbk_str.as_ref())
             .expect("Failed to parse the public key from hexadecimal");
         let pbk_bytes: &[u8; PUBLIC_KEY_LENGTH] = &bytes[..PUBLIC_KEY_LENGTH]
@@ -146,30 +310,274 @@ impl InteractionHandler {
                 application_id: app_id,
                 app_public_key,
                 client: new_c,
-                global_handles: HashMap::new(),
-                component_handles: HashMap::new(),
-                guild_handles: HashMap::new(),
-                data: AnyMap::new(),
+                global_handles: Arc::default(),
+                component_handles: Arc::default(),
+                modal_handles: Arc::default(),
+                guild_handles: Arc::default(),
+                default_allowed_mentions: None,
+                error_handler: default_error_reply,
+                auto_defer: None,
+                middleware: Arc::default(),
+                cooldowns: Arc::default(),
+                owners: Vec::new(),
+                state: Arc::default(),
+                collectors: CollectorRegistry::default(),
+                data: Arc::new(AnyMap::new()),
             }
         } else {
             InteractionHandler {
                 application_id: app_id,
                 app_public_key,
                 client: Client::new(),
-                global_handles: HashMap::new(),
-                component_handles: HashMap::new(),
-                guild_handles: HashMap::new(),
-                data: AnyMap::new(),
+                global_handles: Arc::default(),
+                component_handles: Arc::default(),
+                modal_handles: Arc::default(),
+                guild_handles: Arc::default(),
+                default_allowed_mentions: None,
+                error_handler: default_error_reply,
+                auto_defer: None,
+                middleware: Arc::default(),
+                cooldowns: Arc::default(),
+                owners: Vec::new(),
+                state: Arc::default(),
+                collectors: CollectorRegistry::default(),
+                data: Arc::new(AnyMap::new()),
             }
         }
     }
 
-    /// Add some data. Data can be accessed by handlers with `InteractionHandler.data`
+    /// Add some data. Data can be read by handlers with `InteractionHandler.data`
+    ///
+    /// Use [`InteractionHandler::add_state`] for data that handlers change.
     pub fn add_data<T: Clone>(&mut self, data: T)
     where
         T: Send + 'static + Sync,
     {
-        self.data.insert(data);
+        Arc::make_mut(&mut self.data).insert(data);
+    }
+
+    /// Add shared state. State is never cloned: every handler, deferred or not, sees the same instance.
+    /// Use interior mutability, like a `Mutex` or an atomic, to change it.
+    ///
+    /// Adding state of a type that was added before replaces it.
+    ///
+    /// # Example
+    /// ```ignore
+    /// # use std::sync::atomic::{AtomicU64, Ordering};
+    /// handle.add_state(AtomicU64::new(0));
+    ///
+    /// // In a handler
+    /// let counter = handler.state::<AtomicU64>().unwrap();
+    /// counter.fetch_add(1, Ordering::Relaxed);
+    /// ```
+    pub fn add_state<T: Send + Sync + 'static>(&mut self, state: T) {
+        Arc::make_mut(&mut self.state).insert(TypeId::of::<T>(), Arc::new(state));
+    }
+
+    /// Get state added with [`InteractionHandler::add_state`]
+    pub fn state<T: Send + Sync + 'static>(&self) -> Option<Arc<T>> {
+        self.state.get(&TypeId::of::<T>())?.clone().downcast().ok()
+    }
+
+    /// Wait for a component interaction from within a handler. See [`ComponentCollector`] for the filters.
+    ///
+    /// Waiting collectors get component interactions before handlers added with [`InteractionHandler::add_component_handle`].
+    /// Middleware and cooldowns still run first.
+    pub fn collect_component(&self) -> ComponentCollector {
+        ComponentCollector::new(self.collectors.clone())
+    }
+
+    /// Sets who can be pinged by responses, edits and followups that don't set their own allowed mentions.
+    ///
+    /// # Example
+    /// ```ignore
+    /// # use rusty_interaction::handler::InteractionHandler;
+    /// # use rusty_interaction::types::interaction::AllowedMentions;
+    /// let mut handle = InteractionHandler::new(APP_ID, PUB_KEY, None);
+    /// // Never ping anyone unless a handler explicitly allows it
+    /// handle.set_default_allowed_mentions(AllowedMentions::none());
+    /// ```
+    pub fn set_default_allowed_mentions(&mut self, allowed_mentions: AllowedMentions) {
+        self.default_allowed_mentions = Some(allowed_mentions);
+    }
+
+    /// Sets the function that maps errors returned by handlers to a reply. The error is always logged.
+    ///
+    /// By default, an ephemeral message saying something went wrong is sent. For deferred handlers, the original message is edited instead.
+    ///
+    /// # Example
+    /// ```ignore
+    /// # use rusty_interaction::handler::{HandlerError, InteractionHandler};
+    /// # use rusty_interaction::types::interaction::{Context, InteractionResponse};
+    /// fn error_reply(ctx: &Context, e: &HandlerError) -> InteractionResponse {
+    ///     ctx.respond()
+    ///         .respond_type(InteractionResponseType::ChannelMessageWithSource)
+    ///         .content(format!("Error: {}", e))
+    ///         .is_ephemeral(true)
+    ///         .build()
+    ///         .unwrap()
+    /// }
+    ///
+    /// handle.on_error(error_reply);
+    /// ```
+    pub fn on_error(&mut self, f: ErrorHandlerFunction) {
+        self.error_handler = f;
+    }
+
+    /// Enables automatic deferral. If a handler hasn't finished within `budget`, a deferred response is sent for it.
+    /// The response of the handler is then delivered by editing the original message. Handlers that finish in time reply as usual.
+    ///
+    /// Discord requires a response within 3 seconds, so keep the budget well below that (Ex. 2 seconds).
+    /// Slash commands defer with a new message, component interactions defer with an update of their message.
+    ///
+    /// # Note
+    /// With auto-defer enabled, every handler runs in its own task, on a clone of the `InteractionHandler` that shares its routes, data and state.
+    pub fn auto_defer(&mut self, budget: Duration) {
+        self.auto_defer = Some(budget);
+    }
+
+    /// Adds a [`Middleware`] that runs around every handler. Middleware runs in the order it was added.
+    ///
+    /// See the [`middleware`](crate::middleware) module for an example.
+    pub fn add_middleware(&mut self, middleware: impl Middleware + 'static) {
+        Arc::make_mut(&mut self.middleware).push(Arc::new(middleware));
+    }
+
+    /// Limits how often a command or component can be used. `name` is the name of the command, or the custom ID of the component or modal.
+    ///
+    /// Cooldowns can also be declared on the handler with the `#[cooldown]` attribute.
+    /// Guards like `#[guild_only]` are checked first, so a rejected use doesn't start the cooldown.
+    ///
+    /// # Example
+    /// ```ignore
+    /// # use rusty_interaction::cooldown::{Cooldown, CooldownBucket};
+    /// # use std::time::Duration;
+    /// // Once every 10 seconds per guild
+    /// handle.set_cooldown("backup", Cooldown::new(Duration::from_secs(10), CooldownBucket::Guild));
+    /// ```
+    pub fn set_cooldown(&mut self, name: &'static str, cooldown: Cooldown) {
+        Arc::make_mut(&mut self.cooldowns).insert(name, cooldown);
+    }
+
+    /// Adds a bot owner. Handlers marked with `#[owner_only]` can only be used by owners.
+    pub fn add_owner(&mut self, user: impl Into<UserId>) {
+        self.owners.push(user.into());
+    }
+
+    /// Whether `user` was added with [`InteractionHandler::add_owner`]
+    pub fn is_owner(&self, user: UserId) -> bool {
+        self.owners.contains(&user)
+    }
+
+    #[doc(hidden)]
+    pub fn __error_handler(&self) -> ErrorHandlerFunction {
+        self.error_handler
+    }
+
+    // Used by the attribute macros to check the cooldown set with `set_cooldown`, after the guards.
+    // Returns the reply to send if the command or component of this interaction is on cooldown
+    #[doc(hidden)]
+    pub fn __cooldown(&self, ctx: &Context) -> Option<InteractionResponse> {
+        let data = ctx.interaction.data.as_ref()?;
+        let route = match ctx.interaction.r#type {
+            InteractionType::ApplicationCommand => data.name.as_deref()?,
+            _ => data.custom_id.as_deref()?,
+        };
+
+        self.cooldowns.get(route)?.check_response(ctx)
+    }
+
+    // Calls a handler. With auto-defer enabled, the handler runs in its own task and is deferred if it takes too long
+    async fn call_handler(&self, handler: HandlerFunction, ctx: Context) -> InteractionResponse {
+        let mut response = match self.auto_defer {
+            None => self.run_handler(handler, ctx).await,
+            Some(budget) => {
+                let (tx, mut rx) = oneshot::channel();
+                let ih = self.clone();
+                let c = ctx.clone();
+
+                let deferred_type = if ctx.interaction.r#type == InteractionType::MessageComponent {
+                    InteractionResponseType::DefferedUpdateMessage
+                } else {
+                    InteractionResponseType::DefferedChannelMessageWithSource
+                };
+
+                let deferred = deferred_type.clone();
+                tokio::spawn(async move {
+                    let response = ih.run_handler(handler, ctx).await;
+
+                    // Sending fails if the budget ran out and a deferred response was sent instead
+                    if let Err(mut response) = tx.send(response) {
+                        response.apply_default_allowed_mentions(ih.default_allowed_mentions.as_ref());
+
+                        deliver_late(&c, &deferred, response).await;
+                    }
+                });
+
+                match timeout(budget, &mut rx).await {
+                    Ok(Ok(response)) => response,
+                    Ok(Err(_)) => {
+                        error!("Handler task stopped without a response");
+                        InteractionResponse::new(InteractionResponseType::None, None)
+                    }
+                    Err(_) => {
+                        rx.close();
+                        // The handler may have finished right after the budget ran out
+                        match rx.try_recv() {
+                            Ok(response) => response,
+                            Err(_) => {
+                                debug!("Handler exceeded the auto-defer budget, deferring");
+                                InteractionResponse::new(deferred_type, None)
+                            }
+                        }
+                    }
+                }
+            }
+        };
+        response.apply_default_allowed_mentions(self.default_allowed_mentions.as_ref());
+        response
+    }
+
+    // Calls the handler, with the middleware around it
+    async fn run_handler(&self, handler: HandlerFunction, ctx: Context) -> InteractionResponse {
+        if self.middleware.is_empty() {
+            return handler(self, ctx).await;
+        }
+
+        let mut ran = 0;
+        let mut response = None;
+
+        for m in self.middleware.iter() {
+            ran += 1;
+            if let Some(r) = m.before(&ctx).await {
+                response = Some(r);
+                break;
+            }
+        }
+
+        let mut response = match response {
+            Some(r) => r,
+            // The handler takes the Context, so keep a copy for the after hooks
+            None => handler(self, ctx.clone()).await,
+        };
+
+        for m in self.middleware[..ran].iter().rev() {
+            m.after(&ctx, &mut response).await;
+        }
+        response
+    }
+
+    // The handler registered for the custom ID of a component interaction
+    fn component_handle(&self, ctx: &Context) -> Option<HandlerFunction> {
+        let custom_id = ctx.interaction.data.as_ref()?.custom_id.as_deref()?;
+        self.component_handles.get(custom_id).copied()
+    }
+
+    // Constructs the Context passed to handlers
+    fn context(&self, interaction: Interaction) -> Context {
+        let mut ctx = Context::new(self.client.clone(), interaction);
+        ctx.default_allowed_mentions = self.default_allowed_mentions.clone();
+        ctx
     }
     /// Binds an async function to a **global** command.
     /// Your function must take a [`Context`] as an argument and must return a [`InteractionResponse`].
@@ -213,7 +621,7 @@ impl InteractionHandler {
     /// }
     /// ```
     pub fn add_global_command(&mut self, name: &'static str, func: HandlerFunction) {
-        self.global_handles.insert(name, func);
+        Arc::make_mut(&mut self.global_handles).insert(name, func);
     }
 
     /// Binds an async function to a **component**.
@@ -276,7 +684,15 @@ impl InteractionHandler {
     /// }
     /// ```
     pub fn add_component_handle(&mut self, custom_id: &'static str, func: HandlerFunction) {
-        self.component_handles.insert(custom_id, func);
+        Arc::make_mut(&mut self.component_handles).insert(custom_id, func);
+    }
+
+    /// Binds an async function to a **modal**, identified by the `custom_id` of the modal.
+    /// Your function must take a [`Context`] as an argument and must return a [`InteractionResponse`].
+    ///
+    /// Submitted values can be read with [`Context::modal_value`].
+    pub fn add_modal_handle(&mut self, custom_id: &'static str, func: HandlerFunction) {
+        Arc::make_mut(&mut self.modal_handles).insert(custom_id, func);
     }
 
     pub fn client(&self) -> &Client {
@@ -291,18 +707,26 @@ impl InteractionHandler {
     /// Guild-specific commands are not cached or saved in any way by the handler.
     /// This means that between restarts, updates, crashes, or whatever that causes the app to terminate, the handler 'forgets' which command belonged to which handler.
     pub async fn register_guild_handle(
-        &mut self,
-        guild: impl Into<Snowflake>,
+        &self,
+        guild: impl Into<GuildId>,
         cmd: &ApplicationCommand,
         func: HandlerFunction,
         scope: &ManipulationScope,
     ) -> Result<ApplicationCommand, HttpError> {
         let g = guild.into();
         match scope {
-            ManipulationScope::Local => {
-                self.guild_handles.insert(g, func);
-                Ok(cmd.clone())
-            }
+            ManipulationScope::Local => match cmd.id {
+                // Guild handles are looked up by command ID, so a local registration needs one
+                Some(id) => {
+                    self.guild_handles.lock().unwrap().insert(id, func);
+                    Ok(cmd.clone())
+                }
+                None => Err(HttpError {
+                    code: 0,
+                    message: "Cannot register a local guild handle for a command without an ID."
+                        .to_string(),
+                }),
+            },
             ManipulationScope::Discord | ManipulationScope::All => {
                 let url = format!(
                     "{}/applications/{}/guilds/{}/commands",
@@ -317,7 +741,7 @@ impl InteractionHandler {
                     if let Some(id) = a.id {
                         if scope == &ManipulationScope::All {
                             // Already overwrites current key if it exists, so no need to check.
-                            self.guild_handles.insert(id, func);
+                            self.guild_handles.lock().unwrap().insert(id, func);
                         }
 
                         Ok(a)
@@ -338,9 +762,9 @@ impl InteractionHandler {
     #[cfg_attr(docsrs, doc(cfg(feature = "extended-handler")))]
     /// Remove a guild handle
     pub async fn deregister_guild_handle(
-        &mut self,
-        guild: impl Into<Snowflake>,
-        id: impl Into<Snowflake>,
+        &self,
+        guild: impl Into<GuildId>,
+        id: impl Into<CommandId>,
         scope: &ManipulationScope,
     ) -> Result<(), HttpError> {
         let i = id.into();
@@ -348,7 +772,7 @@ impl InteractionHandler {
 
         match scope {
             ManipulationScope::Local => {
-                self.guild_handles.remove(&i);
+                self.guild_handles.lock().unwrap().remove(&i);
                 Ok(())
             }
             ManipulationScope::All | ManipulationScope::Discord => {
@@ -364,7 +788,7 @@ impl InteractionHandler {
 
                 expect_specific_api_response!(r, StatusCode::NO_CONTENT, {
                     if scope == &ManipulationScope::All {
-                        self.guild_handles.remove(&i);
+                        self.guild_handles.lock().unwrap().remove(&i);
                     }
 
                     Ok(())
@@ -378,7 +802,7 @@ impl InteractionHandler {
     /// Override a bunch of permissions for commands in a guild.
     pub async fn override_guild_permissions(
         &self,
-        guild_id: impl Into<Snowflake>,
+        guild_id: impl Into<GuildId>,
         overrides: &[ApplicationCommandPermissionBatch],
     ) -> Result<(), HttpError> {
         let url = format!(
@@ -398,8 +822,8 @@ impl InteractionHandler {
     /// Add a permission override for a guild command
     pub async fn edit_guild_command_permissions(
         &self,
-        guild_id: impl Into<Snowflake>,
-        appcmd_id: impl Into<Snowflake>,
+        guild_id: impl Into<GuildId>,
+        appcmd_id: impl Into<CommandId>,
         permission_override: &ApplicationCommandPermission,
     ) -> Result<(), HttpError> {
         let url = format!(
@@ -415,53 +839,173 @@ impl InteractionHandler {
         expect_successful_api_response!(res, Ok(()))
     }
 
-    /// Entry point function for handling `Interactions`
-    pub async fn interaction(&mut self, req: HttpRequest, body: String) -> Result<HttpResponse> {
-        // Check for good content type --> must be application/json
+    #[cfg(feature = "extended-handler")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "extended-handler")))]
+    /// List the [`Sku`]s of this application
+    pub async fn list_skus(&self) -> Result<Vec<Sku>, HttpError> {
+        let url = format!(
+            "{}/applications/{}/skus",
+            crate::BASE_URL,
+            self.application_id
+        );
 
-        if let Some(ct) = req.headers().get("Content-Type") {
-            if ct != "application/json" {
-                debug!(
-                    "Incoming interaction rejected, bad Content-Type specified. Origin: {:?}",
-                    req.connection_info().realip_remote_addr()
-                );
-                return ERROR_RESPONSE!(400, "Bad Content-Type");
-            }
-        } else {
+        let r = self.client.get(&url).send().await;
+
+        expect_successful_api_response_and_return!(r, Vec<Sku>, s, Ok(s))
+    }
+
+    #[cfg(feature = "extended-handler")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "extended-handler")))]
+    /// List the [`Entitlement`]s of this application, filtered by an [`EntitlementFilter`]
+    pub async fn list_entitlements(
+        &self,
+        filter: &EntitlementFilter,
+    ) -> Result<Vec<Entitlement>, HttpError> {
+        let url = format!(
+            "{}/applications/{}/entitlements",
+            crate::BASE_URL,
+            self.application_id
+        );
+
+        let r = self
+            .client
+            .get(&url)
+            .query(&filter.to_query())
+            .send()
+            .await;
+
+        expect_successful_api_response_and_return!(r, Vec<Entitlement>, e, Ok(e))
+    }
+
+    #[cfg(feature = "extended-handler")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "extended-handler")))]
+    /// Mark a consumable [`Entitlement`] as consumed
+    pub async fn consume_entitlement(
+        &self,
+        entitlement_id: impl Into<EntitlementId>,
+    ) -> Result<(), HttpError> {
+        let url = format!(
+            "{}/applications/{}/entitlements/{}/consume",
+            crate::BASE_URL,
+            self.application_id,
+            entitlement_id.into()
+        );
+
+        let r = self.client.post(&url).send().await;
+
+        expect_specific_api_response!(r, StatusCode::NO_CONTENT, Ok(()))
+    }
+
+    #[cfg(feature = "extended-handler")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "extended-handler")))]
+    /// Create a test [`Entitlement`] for a user or guild. Test entitlements can be used to test premium features without paying.
+    pub async fn create_test_entitlement(
+        &self,
+        entitlement: &TestEntitlement,
+    ) -> Result<Entitlement, HttpError> {
+        let url = format!(
+            "{}/applications/{}/entitlements",
+            crate::BASE_URL,
+            self.application_id
+        );
+
+        let r = self.client.post(&url).json(entitlement).send().await;
+
+        expect_successful_api_response_and_return!(r, Entitlement, e, Ok(e))
+    }
+
+    #[cfg(feature = "extended-handler")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "extended-handler")))]
+    /// Delete a test [`Entitlement`]
+    pub async fn delete_test_entitlement(
+        &self,
+        entitlement_id: impl Into<EntitlementId>,
+    ) -> Result<(), HttpError> {
+        let url = format!(
+            "{}/applications/{}/entitlements/{}",
+            crate::BASE_URL,
+            self.application_id,
+            entitlement_id.into()
+        );
+
+        let r = self.client.delete(&url).send().await;
+
+        expect_specific_api_response!(r, StatusCode::NO_CONTENT, Ok(()))
+    }
+
+    /// Entry point function for handling `Interactions` with actix-web
+    ///
+    /// This is a thin wrapper around [`InteractionHandler::process`].
+    #[cfg(feature = "handler")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "handler")))]
+    pub async fn interaction(
+        &self,
+        req: HttpRequest,
+        body: String,
+    ) -> actix_web::Result<HttpResponse> {
+        let (status, body) = self.process(req.headers(), &body).await;
+
+        if (400..500).contains(&status) {
             debug!(
-                "Incoming interaction rejected, no Content-Type specified. Origin: {:?}",
+                "Incoming interaction rejected. Origin: {:?}",
                 req.connection_info().realip_remote_addr()
             );
-            return ERROR_RESPONSE!(400, "Bad Content-Type");
         }
 
-        let se = get_header(&req, "X-Signature-Ed25519");
-        let st = get_header(&req, "X-Signature-Timestamp");
+        let mut response =
+            HttpResponse::build(actix_web::http::StatusCode::from_u16(status).unwrap());
+        if body.is_empty() {
+            Ok(response.finish())
+        } else {
+            Ok(response.content_type("application/json").body(body))
+        }
+    }
+
+    /// Handle an incoming interaction, independent of any web framework.
+    ///
+    /// Takes the request headers and body, and returns the status code and body of the response.
+    /// A non-empty body is always JSON, so the response should have an `application/json` content type.
+    /// Use this to serve interactions from a framework that this crate has no adapter for.
+    ///
+    /// Interactions can be processed concurrently.
+    pub async fn process<H: RequestHeaders + ?Sized>(
+        &self,
+        headers: &H,
+        body: &str,
+    ) -> (u16, String) {
+        // Check for good content type --> must be application/json
+        match headers.header("Content-Type") {
+            Some("application/json") => (),
+            Some(_) => {
+                debug!("Incoming interaction rejected, bad Content-Type specified.");
+                return ERROR_RESPONSE!(400, "Bad Content-Type");
+            }
+            None => {
+                debug!("Incoming interaction rejected, no Content-Type specified.");
+                return ERROR_RESPONSE!(400, "Bad Content-Type");
+            }
+        }
+
+        let se = headers.header("X-Signature-Ed25519");
+        let st = headers.header("X-Signature-Timestamp");
 
         // TODO: Domain check might be a good one.
 
         if let Some((se, st)) = se.zip(st) {
             // Verify timestamp + body against given signature
-            if !verify_discord_message(self.app_public_key, se, st, &body).is_ok() {
+            if !verify_discord_message(self.app_public_key, se, st, body).is_ok() {
                 // Verification failed, reject.
-                // TODO: Switch error response
-                debug!(
-                    "Incoming interaction rejected, invalid signature. Origin: {:?}",
-                    req.connection_info().realip_remote_addr()
-                );
+                debug!("Incoming interaction rejected, invalid signature.");
                 return ERROR_RESPONSE!(401, "Invalid request signature");
             }
         } else {
             // If proper headers are not present reject.
-            debug!(
-                "Incoming interaction rejected, missing headers. Origin: {:?}",
-                req.connection_info().realip_remote_addr()
-            );
+            debug!("Incoming interaction rejected, missing headers.");
             return ERROR_RESPONSE!(400, "Bad signature data");
         }
 
         // Security checks passed, try deserializing request to Interaction.
-        match serde_json::from_str::<Interaction>(&body) {
+        match serde_json::from_str::<Interaction>(body) {
             Err(e) => {
                 // It's probably bad on our end if this code is reached.
                 error!("Failed to decode interaction! Error: {}", e);
@@ -474,9 +1018,7 @@ impl InteractionHandler {
                         let response =
                             InteractionResponse::new(InteractionResponseType::Pong, None);
                         debug!("Got a ping, responding with pong.");
-                        return Ok(HttpResponse::build(StatusCode::OK)
-                            .content_type("application/json")
-                            .json(response));
+                        match_handler_response!(response)
                     }
 
                     InteractionType::ApplicationCommand => {
@@ -488,12 +1030,18 @@ impl InteractionHandler {
                         };
 
                         // Check for matches in guild handler map. Unwrapping because this should always contain an ID
-                        if let Some(handler) = self.guild_handles.get(data.id.as_ref().unwrap()) {
+                        let guild_handler = self
+                            .guild_handles
+                            .lock()
+                            .unwrap()
+                            .get(data.id.as_ref().unwrap())
+                            .copied();
+                        if let Some(handler) = guild_handler {
                             // construct a Context
-                            let ctx = Context::new(self.client.clone(), interaction);
+                            let ctx = self.context(interaction);
 
                             // Call the handler
-                            let response = handler(self, ctx).await;
+                            let response = self.call_handler(handler, ctx).await;
 
                             match_handler_response!(response)
                         }
@@ -502,10 +1050,10 @@ impl InteractionHandler {
                             data.name.as_ref().unwrap().as_str(), /* Don't question it */
                         ) {
                             // construct a Context
-                            let ctx = Context::new(self.client.clone(), interaction);
+                            let ctx = self.context(interaction);
 
                             // Call the handler
-                            let response = handler(self, ctx).await;
+                            let response = self.call_handler(*handler, ctx).await;
 
                             match_handler_response!(response)
                         }
@@ -519,6 +1067,40 @@ impl InteractionHandler {
                         }
                     }
                     InteractionType::MessageComponent => {
+                        if interaction.data.is_none() {
+                            error!("Failed to unwrap Interaction!");
+                            return ERROR_RESPONSE!(500, "Failed to unwrap");
+                        }
+
+                        // construct a Context
+                        let ctx = self.context(interaction);
+                        let data = ctx.interaction.data.as_ref().unwrap();
+                        let custom_id = data.custom_id.clone().unwrap_or_default();
+                        let static_handler = self.component_handle(&ctx);
+
+                        // Handlers waiting for this component come before the static routes
+                        let handler = if self.collectors.is_waiting(&ctx) {
+                            Some(collected as HandlerFunction)
+                        } else {
+                            static_handler
+                        };
+
+                        if let Some(handler) = handler {
+                            // Call the handler
+                            let response = self.call_handler(handler, ctx).await;
+
+                            // The collector stopped waiting before it got the component, and there is no static route either
+                            if static_handler.is_none() && response.r#type == InteractionResponseType::None {
+                                error!("No associated handler found for {}", custom_id);
+                                return ERROR_RESPONSE!(501, "No associated handler found");
+                            }
+                            match_handler_response!(response)
+                        } else {
+                            error!("No associated handler found for {}", custom_id);
+                            ERROR_RESPONSE!(501, "No associated handler found")
+                        }
+                    }
+                    InteractionType::ModalSubmit => {
                         let data = if let Some(ref data) = interaction.data {
                             data
                         } else {
@@ -527,14 +1109,14 @@ impl InteractionHandler {
                         };
 
                         if let Some(handler) = self
-                            .component_handles
+                            .modal_handles
                             .get(data.custom_id.as_ref().unwrap().as_str())
                         {
                             // construct a Context
-                            let ctx = Context::new(self.client.clone(), interaction);
+                            let ctx = self.context(interaction);
 
                             // Call the handler
-                            let response = handler(self, ctx).await;
+                            let response = self.call_handler(*handler, ctx).await;
 
                             match_handler_response!(response)
                         } else {
@@ -552,43 +1134,41 @@ impl InteractionHandler {
 
     /// This is a predefined function that starts an `actix_web::HttpServer` and binds `self.interaction` to `/api/discord/interactions`.
     /// Note that you'll eventually have to switch to an HTTPS server. This function does not provide this.
+    ///
+    /// Use [`InteractionHandler::serve`] to choose the address, path and other settings.
+    #[cfg(feature = "handler")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "handler")))]
     pub async fn run(self, port: u16) -> std::io::Result<()> {
-        let data = web::Data::new(Mutex::new(self));
-        HttpServer::new(move || {
-            App::new().app_data(data.clone()).route(
-                "/api/discord/interactions",
-                web::post().to(
-                    |data: web::Data<Mutex<InteractionHandler>>, req: HttpRequest, body: String| async move {
-                        data.lock().unwrap().interaction(req, body).await
-                    },
-                ),
-            )
-        })
-        .bind(format!("0.0.0.0:{}", port))?
-        .run()
-        .await
+        let config = InteractionServerConfig::new().address(format!("0.0.0.0:{}", port));
+        self.serve(config).await
     }
 
     /// Same as [`InteractionHandler::run`] but starts a server with SSL/TLS.
+    #[cfg(feature = "handler")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "handler")))]
     pub async fn run_ssl(self, server_conf: ServerConfig, port: u16) -> std::io::Result<()> {
-        let data = web::Data::new(Mutex::new(self));
-        HttpServer::new(move || {
-            App::new().app_data(data.clone()).route(
-                "/api/discord/interactions",
-                web::post().to(
-                    |data: web::Data<Mutex<InteractionHandler>>, req: HttpRequest, body: String| async move {
-                        data.lock().unwrap().interaction(req, body).await
-                    },
-                ),
-            )
-        })
-        .bind_rustls(format!("0.0.0.0:{}", port), server_conf)?
-        .run()
-        .await
+        let config = InteractionServerConfig::new().address(format!("0.0.0.0:{}", port));
+        self.serve_tls(config, server_conf).await
     }
 }
 
-/// Simpler header getter from a HTTP request
-fn get_header<'a>(req: &'a HttpRequest, header: &str) -> Option<&'a str> {
-    req.headers().get(header)?.to_str().ok()
+/// The headers [`InteractionHandler::process`] reads from a request
+pub trait RequestHeaders {
+    /// Get the value of a header by its case-insensitive name
+    fn header(&self, name: &str) -> Option<&str>;
+}
+
+#[cfg(feature = "handler")]
+impl RequestHeaders for actix_web::http::header::HeaderMap {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.get(name)?.to_str().ok()
+    }
+}
+
+impl<S: std::hash::BuildHasher> RequestHeaders for HashMap<String, String, S> {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
 }
@@ -23,23 +23,54 @@ pub mod types;
 pub mod security;
 
 /// Provides an entire handler to handle Discord interactions.
-#[cfg(any(feature = "handler", feature = "extended-handler"))]
-#[cfg_attr(docsrs, doc(cfg(feature = "handler")))]
+#[cfg(feature = "handler-core")]
+#[cfg_attr(docsrs, doc(cfg(feature = "handler-core")))]
 pub mod handler;
-#[cfg(any(feature = "handler", feature = "extended-handler"))]
+/// Extract handler parameters from an interaction.
+#[cfg(feature = "handler-core")]
+#[cfg_attr(docsrs, doc(cfg(feature = "handler-core")))]
+pub mod extract;
+/// Run code around every handler.
+#[cfg(feature = "handler-core")]
+#[cfg_attr(docsrs, doc(cfg(feature = "handler-core")))]
+pub mod middleware;
+/// Limit how often commands and components can be used.
+#[cfg(feature = "handler-core")]
+#[cfg_attr(docsrs, doc(cfg(feature = "handler-core")))]
+pub mod cooldown;
+/// Check who can use a handler, and where.
+#[cfg(feature = "handler-core")]
+#[cfg_attr(docsrs, doc(cfg(feature = "handler-core")))]
+pub mod guard;
+/// Wait for component interactions inside a handler.
+#[cfg(feature = "handler-core")]
+#[cfg_attr(docsrs, doc(cfg(feature = "handler-core")))]
+pub mod collector;
+/// Serve interactions with actix-web.
+#[cfg(feature = "handler")]
+#[cfg_attr(docsrs, doc(cfg(feature = "handler")))]
+pub mod server;
+/// Serve interactions with a tower `Service`.
+#[cfg(feature = "tower")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tower")))]
+pub mod tower;
+#[cfg(feature = "handler")]
 #[cfg_attr(docsrs, doc(cfg(feature = "handler")))]
 pub use actix;
 
-#[cfg(any(feature = "handler", feature = "extended-handler"))]
-#[cfg_attr(docsrs, doc(cfg(feature = "handler")))]
+#[cfg(feature = "handler-core")]
+#[cfg_attr(docsrs, doc(cfg(feature = "handler-core")))]
 pub use log;
 
-#[cfg(any(feature = "handler", feature = "extended-handler"))]
-#[cfg_attr(docsrs, doc(cfg(feature = "handler")))]
+#[cfg(feature = "handler-core")]
+#[cfg_attr(docsrs, doc(cfg(feature = "handler-core")))]
 pub use attributes::*;
 
 #[cfg(all(test, feature = "security"))]
 mod tests;
+// The attribute macros refer to this crate by name, also in its own tests
+#[cfg(all(test, feature = "handler-core"))]
+extern crate self as rusty_interaction;
 
 /// A trait for defining builder patterns.
 pub trait Builder<T> {
@@ -112,7 +143,7 @@ macro_rules! expect_specific_api_response {
 #[macro_export]
 #[doc(hidden)]
 macro_rules! expect_successful_api_response_and_return {
-    ($response:ident, $struc:ident, $retval:ident, $succret:expr) => {
+    ($response:ident, $struc:ty, $retval:ident, $succret:expr) => {
         match $response {
             Err(e) => {
                 debug!("Discord API request failed: {:#?}", e);
@@ -1,13 +1,10 @@
-/// Macro that generates an `HttpResponse` containing a message serialized in JSON
+/// Macro that generates a status code and a message serialized in JSON
 #[macro_export]
 #[doc(hidden)]
 macro_rules! ERROR_RESPONSE {
     ($status:expr, $message:expr) => {{
         let emsg = $crate::types::MessageError::new(::std::string::String::from($message));
 
-        Ok(::actix_web::HttpResponse::build(
-            ::actix_web::http::StatusCode::from_u16($status).unwrap(),
-        )
-        .json(emsg))
+        ($status, ::serde_json::to_string(&emsg).unwrap_or_default())
     }};
 }
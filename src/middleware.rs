@@ -0,0 +1,37 @@
+//! Middleware runs around every handler the [`InteractionHandler`](crate::handler::InteractionHandler) dispatches to.
+//!
+//! Use it for behavior that applies to all commands, components and modals, like logging, metrics or maintenance mode.
+//! Middleware is added with [`InteractionHandler::add_middleware`](crate::handler::InteractionHandler::add_middleware). The first added middleware is the outermost:
+//! its [`Middleware::before`] runs first and its [`Middleware::after`] runs last.
+//!
+//! ```ignore
+//! use rusty_interaction::middleware::{async_trait, Middleware};
+//!
+//! struct Maintenance;
+//!
+//! #[async_trait]
+//! impl Middleware for Maintenance {
+//!     async fn before(&self, ctx: &Context) -> Option<InteractionResponse> {
+//!         Some(ctx.respond().content("Down for maintenance").is_ephemeral(true).build().unwrap())
+//!     }
+//! }
+//!
+//! handle.add_middleware(Maintenance);
+//! ```
+
+/// Re-exported to implement [`Middleware`]
+pub use async_trait::async_trait;
+
+use crate::types::interaction::{Context, InteractionResponse};
+
+/// A hook around handler dispatch
+#[async_trait]
+pub trait Middleware: Send + Sync {
+    /// Called before the handler. Return `Some` to respond with it without calling the handler or any later middleware.
+    async fn before(&self, _ctx: &Context) -> Option<InteractionResponse> {
+        None
+    }
+
+    /// Called with the response of the handler, or of a later middleware that responded in [`Middleware::before`]
+    async fn after(&self, _ctx: &Context, _response: &mut InteractionResponse) {}
+}
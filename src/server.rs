@@ -0,0 +1,155 @@
+//! Serve interactions with actix-web, either with a server configured by [`InteractionServerConfig`],
+//! or mounted into an existing actix-web `App` next to other routes.
+//!
+//! ```ignore
+//! let interactions = handle.into_shared();
+//!
+//! HttpServer::new(move || {
+//!     App::new()
+//!         .configure(interactions.configure("/api/discord/interactions"))
+//!         .route("/health", web::get().to(|| async { "OK" }))
+//! })
+//! .bind("127.0.0.1:8080")?
+//! .run()
+//! .await
+//! ```
+use actix_web::{web, App, HttpRequest, HttpResponse, HttpServer, Resource, Result};
+use rustls::ServerConfig;
+
+use crate::handler::InteractionHandler;
+
+#[derive(Clone, Debug)]
+/// Where and how [`InteractionHandler::serve`] serves interactions
+pub struct InteractionServerConfig {
+    address: String,
+    path: String,
+    workers: Option<usize>,
+    body_limit: usize,
+}
+
+impl Default for InteractionServerConfig {
+    fn default() -> Self {
+        Self {
+            address: String::from("0.0.0.0:10080"),
+            path: String::from("/api/discord/interactions"),
+            workers: None,
+            body_limit: 256 * 1024,
+        }
+    }
+}
+
+impl InteractionServerConfig {
+    /// Creates a config with the defaults: serve `/api/discord/interactions` on `0.0.0.0:10080`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The address to bind to (Ex. `127.0.0.1:8080`)
+    pub fn address(mut self, address: impl Into<String>) -> Self {
+        self.address = address.into();
+        self
+    }
+
+    /// The path to serve interactions on. Must start with a `/`
+    pub fn path(mut self, path: impl Into<String>) -> Self {
+        self.path = path.into();
+        self
+    }
+
+    /// The number of worker threads. Defaults to the number of CPU cores
+    pub fn workers(mut self, workers: usize) -> Self {
+        self.workers = Some(workers);
+        self
+    }
+
+    /// The largest request body accepted, in bytes. Defaults to 256 KiB
+    pub fn body_limit(mut self, limit: usize) -> Self {
+        self.body_limit = limit;
+        self
+    }
+}
+
+#[derive(Clone, Debug)]
+/// An [`InteractionHandler`] shared between the workers of an actix-web server.
+///
+/// Created with [`InteractionHandler::into_shared`]. Clones share the same handler.
+pub struct SharedHandler {
+    handler: web::Data<InteractionHandler>,
+}
+
+impl SharedHandler {
+    /// The interaction endpoint at `path`, to add to an `App` or `Scope` with `.service()`
+    pub fn resource(&self, path: &str) -> Resource {
+        web::resource(path)
+            .app_data(self.handler.clone())
+            .route(web::post().to(endpoint))
+    }
+
+    /// Adds the interaction endpoint at `path`. Use with `App::configure` or `Scope::configure`
+    pub fn configure(&self, path: &str) -> impl FnOnce(&mut web::ServiceConfig) {
+        let resource = self.resource(path);
+
+        move |cfg| {
+            cfg.service(resource);
+        }
+    }
+}
+
+async fn endpoint(
+    handler: web::Data<InteractionHandler>,
+    req: HttpRequest,
+    body: String,
+) -> Result<HttpResponse> {
+    handler.interaction(req, body).await
+}
+
+impl InteractionHandler {
+    /// Share the handler between the workers of an actix-web server, to mount it into your own `App`
+    pub fn into_shared(self) -> SharedHandler {
+        SharedHandler {
+            handler: web::Data::new(self),
+        }
+    }
+
+    /// Start an actix-web server that serves interactions as configured
+    pub async fn serve(self, config: InteractionServerConfig) -> std::io::Result<()> {
+        self.serve_with(config, None).await
+    }
+
+    /// Same as [`InteractionHandler::serve`] but starts a server with SSL/TLS.
+    pub async fn serve_tls(
+        self,
+        config: InteractionServerConfig,
+        tls: ServerConfig,
+    ) -> std::io::Result<()> {
+        self.serve_with(config, Some(tls)).await
+    }
+
+    // Starts the server for `serve` and `serve_tls`
+    async fn serve_with(
+        self,
+        config: InteractionServerConfig,
+        tls: Option<ServerConfig>,
+    ) -> std::io::Result<()> {
+        let shared = self.into_shared();
+        let c = config.clone();
+
+        let server = HttpServer::new(move || {
+            App::new().service(
+                shared
+                    .resource(&c.path)
+                    .app_data(web::PayloadConfig::new(c.body_limit)),
+            )
+        });
+        let server = match config.workers {
+            Some(w) => server.workers(w),
+            None => server,
+        };
+        let server = match tls {
+            Some(tls) => server.bind_rustls(&config.address, tls)?,
+            None => server.bind(&config.address)?,
+        };
+
+        server.run().await
+    }
+}
@@ -1,21 +1,27 @@
 use std::convert::TryFrom;
 use crate::security::*;
-#[cfg(feature = "handler")]
+#[cfg(feature = "handler-core")]
 use crate::handler::InteractionHandler;
 
+#[cfg(feature = "handler-core")]
+use crate::types::interaction::Context;
 #[cfg(feature = "handler")]
 use crate::types::interaction::{
-    Context, InteractionResponse, InteractionResponseBuilder, InteractionResponseType,
+    InteractionResponse, InteractionResponseBuilder, InteractionResponseType,
 };
 #[cfg(feature = "handler")]
 use crate::*;
 
 #[cfg(feature = "handler")]
-use actix_web::test;
+use actix_web::test as actix_test;
+#[cfg(feature = "handler")]
+use actix_web::{web, App, HttpRequest};
 use ed25519_dalek::VerifyingKey;
 
 #[cfg(feature = "handler")]
 use log::error;
+#[cfg(feature = "handler-core")]
+use std::collections::HashMap;
 
 const TEST_PUB_KEY: &str = "82d8d97fe0641e68a1b0b11220f05e9ea0539a0cdc002119d4a9e9e025aba1e9";
 /*------------------------------
@@ -72,6 +78,717 @@ fn crypto_verify_test_fail() {
         }
     }
 }
+/*------------------------------
+TYPES TESTS
+*/
+#[test]
+// Auto-populated select menu interaction with resolved data
+fn types_auto_select_menu_interaction_test() {
+    use crate::types::components::ComponentType;
+    use crate::types::interaction::{Interaction, InteractionType};
+
+    let payload = "{\"type\":3,\"token\":\"awQabcabc\",\"id\":\"786008729715212338\",\"guild_id\":\"290926798626357999\",\"channel_id\":\"645027906669510667\",\"data\":{\"component_type\":8,\"custom_id\":\"channels\",\"values\":[\"645027906669510667\"],\"resolved\":{\"channels\":{\"645027906669510667\":{\"id\":\"645027906669510667\",\"type\":0,\"name\":\"general\",\"permissions\":\"2147483647\",\"parent_id\":null}},\"members\":{\"317209107000066050\":{\"roles\":[],\"joined_at\":\"2017-03-13T19:19:14.040000+00:00\",\"premium_since\":null,\"pending\":false,\"nick\":null}}}}}";
+
+    let i: Interaction = serde_json::from_str(payload).expect("Failed to decode interaction");
+    assert_eq!(i.r#type, InteractionType::MessageComponent);
+
+    let data = i.data.unwrap();
+    assert_eq!(data.component_type, Some(ComponentType::ChannelSelect));
+    assert_eq!(data.selected_values(), ["645027906669510667"]);
+
+    let resolved = data.resolved.unwrap();
+    let channels = resolved.channels.unwrap();
+    assert_eq!(channels[&645027906669510667].name.as_deref(), Some("general"));
+    assert!(resolved.members.unwrap().contains_key(&317209107000066050));
+}
+
+#[test]
+// Modal submit interaction with nested text inputs
+fn types_modal_submit_interaction_test() {
+    use crate::types::interaction::{Interaction, InteractionType};
+
+    let payload = "{\"type\":5,\"token\":\"awQabcabc\",\"id\":\"786008729715212338\",\"channel_id\":\"645027906669510667\",\"data\":{\"custom_id\":\"feedback\",\"components\":[{\"type\":1,\"components\":[{\"type\":4,\"custom_id\":\"name\",\"value\":\"C0der\"}]},{\"type\":1,\"components\":[{\"type\":4,\"custom_id\":\"about\",\"value\":\"\"}]}]}}";
+
+    let i: Interaction = serde_json::from_str(payload).expect("Failed to decode interaction");
+    assert_eq!(i.r#type, InteractionType::ModalSubmit);
+
+    let data = i.data.unwrap();
+    assert_eq!(data.custom_id.as_deref(), Some("feedback"));
+    assert_eq!(data.modal_value("name"), Some("C0der"));
+    assert_eq!(data.modal_value("about"), Some(""));
+    assert_eq!(data.modal_value("missing"), None);
+    assert!(data.selected_values().is_empty());
+}
+
+#[test]
+// Slash command options of a subcommand are deserialized into a typed struct
+fn types_command_options_test() {
+    use crate::types::id::UserId;
+    use crate::types::interaction::Interaction;
+
+    #[derive(serde::Deserialize)]
+    struct BanArgs {
+        user: UserId,
+        days: i64,
+        reason: Option<String>,
+    }
+
+    let payload = "{\"type\":2,\"token\":\"awQabcabc\",\"id\":\"786008729715212338\",\"data\":{\"name\":\"mod\",\"options\":[{\"name\":\"ban\",\"type\":1,\"options\":[{\"name\":\"user\",\"type\":6,\"value\":\"80351110224678912\"},{\"name\":\"days\",\"type\":4,\"value\":7}]}]}}";
+
+    let i: Interaction = serde_json::from_str(payload).expect("Failed to decode interaction");
+    let data = i.data.unwrap();
+    assert_eq!(data.leaf_options().len(), 2);
+
+    let args: BanArgs = data.deserialize_options().unwrap();
+    assert_eq!(args.user, 80351110224678912);
+    assert_eq!(args.days, 7);
+    assert_eq!(args.reason, None);
+}
+
+#[test]
+// Component interactions carry the message the component was on
+fn types_component_message_test() {
+    use crate::types::id::{InteractionId, MessageId};
+    use crate::types::interaction::{Interaction, InteractionType};
+
+    let payload = "{\"type\":3,\"token\":\"awQabcabc\",\"id\":\"786008729715212338\",\"message\":{\"id\":\"846462639134605312\",\"channel_id\":\"645027906669510667\",\"content\":\"Are you sure?\",\"interaction_metadata\":{\"id\":\"786008729715212300\",\"type\":2,\"user\":{\"id\":\"317209107000066050\",\"username\":\"C0der\",\"discriminator\":\"1337\"}},\"components\":[]},\"data\":{\"custom_id\":\"yes\",\"component_type\":2}}";
+
+    let i: Interaction = serde_json::from_str(payload).expect("Failed to decode interaction");
+    let message = i.message.unwrap();
+    assert_eq!(message.id, MessageId::from(846462639134605312));
+    assert_eq!(message.content.as_deref(), Some("Are you sure?"));
+
+    let metadata = message.interaction_metadata.unwrap();
+    assert_eq!(metadata.id, InteractionId::from(786008729715212300));
+    assert_eq!(metadata.r#type, InteractionType::ApplicationCommand);
+}
+
+#[test]
+// The permission string of an interaction member is parsed into flags
+fn types_member_permissions_test() {
+    use crate::types::guild::Permissions;
+    use crate::types::interaction::Interaction;
+
+    let payload = "{\"type\":2,\"token\":\"awQabcabc\",\"id\":\"786008729715212338\",\"guild_id\":\"290926798626357999\",\"member\":{\"user\":{\"id\":\"317209107000066050\",\"username\":\"C0der\",\"discriminator\":\"1337\"},\"roles\":[],\"joined_at\":\"2017-03-13T19:19:14.040000+00:00\",\"permissions\":\"1152921504606847008\"}}";
+
+    let i: Interaction = serde_json::from_str(payload).expect("Failed to decode interaction");
+    let perms = i.member.unwrap().permission_set().unwrap();
+    assert!(perms.contains(Permissions::MANAGE_GUILD));
+    assert!(!perms.contains(Permissions::ADMINISTRATOR));
+    // Unknown bits are kept
+    assert_eq!(perms.bits(), (1 << 60) | (1 << 5));
+}
+
+#[test]
+// Entitlements sent along with an interaction
+fn types_interaction_entitlements_test() {
+    use crate::types::interaction::Interaction;
+
+    let payload = "{\"type\":2,\"token\":\"awQabcabc\",\"id\":\"786008729715212338\",\"data\":{\"name\":\"test\",\"id\":\"771825006014889984\"},\"entitlements\":[{\"id\":\"1019653849998299136\",\"sku_id\":\"1019475255913222144\",\"application_id\":\"1019370614521200640\",\"user_id\":\"771129655544643584\",\"type\":8,\"deleted\":false,\"starts_at\":\"2022-09-14T17:00:18.704163+00:00\",\"ends_at\":null,\"consumed\":false},{\"id\":\"1019653849998299137\",\"sku_id\":\"1019475255913222145\",\"application_id\":\"1019370614521200640\",\"user_id\":\"771129655544643584\",\"type\":1,\"deleted\":false,\"consumed\":true}]}";
+
+    let i: Interaction = serde_json::from_str(payload).expect("Failed to decode interaction");
+    let entitlements = i.entitlements.unwrap();
+
+    assert_eq!(entitlements.len(), 2);
+    assert!(entitlements[0].is_active());
+    // Consumed entitlements no longer grant access
+    assert!(!entitlements[1].is_active());
+}
+
+#[cfg(feature = "builder")]
+#[test]
+// Embeds exceeding Discord's limits are rejected when building
+fn types_embed_limits_test() {
+    use crate::types::embed::*;
+    use crate::Builder;
+
+    assert!(EmbedBuilder::default()
+        .title("Title")
+        .add_field(EmbedField::default().name("Name").value("Value"))
+        .build()
+        .is_ok());
+
+    let res = EmbedBuilder::default().title("a".repeat(257)).build();
+    assert_eq!(res, Err(EmbedBuilderError::TitleTooLong));
+
+    let res = EmbedBuilder::default()
+        .add_field(EmbedField::default().name("Name").value("Value"))
+        .add_field(EmbedField::default().name("Name").value("a".repeat(1025)))
+        .build();
+    assert_eq!(res, Err(EmbedBuilderError::FieldValueTooLong(1)));
+
+    let mut b = EmbedBuilder::default();
+    for _ in 0..26 {
+        b = b.add_field(EmbedField::default().name("Name").value("Value"));
+    }
+    assert_eq!(b.build(), Err(EmbedBuilderError::TooManyFields));
+
+    // Every part is within its own limit, but the total is over 6000 characters
+    let res = EmbedBuilder::default()
+        .description("a".repeat(4096))
+        .footer(EmbedFooter::default().text("a".repeat(2048)))
+        .build();
+    assert_eq!(res, Err(EmbedBuilderError::TotalTooLong));
+}
+
+#[cfg(feature = "builder")]
+#[test]
+// Default values on an auto-populated select menu must match its type
+fn types_auto_select_menu_builder_test() {
+    use crate::types::channel::ChannelType;
+    use crate::types::components::*;
+    use crate::Builder;
+
+    let ok = ComponentAutoSelectMenuBuilder::default()
+        .menu_type(&ComponentType::ChannelSelect)
+        .custom_id("channels")
+        .add_channel_type(ChannelType::GuildText)
+        .add_default_value(SelectDefaultValue::channel(645027906669510667u64))
+        .build();
+    assert!(ok.is_ok());
+
+    let mismatch = ComponentAutoSelectMenuBuilder::default()
+        .menu_type(&ComponentType::RoleSelect)
+        .custom_id("roles")
+        .add_default_value(SelectDefaultValue::user(317209107000066050u64))
+        .build();
+    assert!(matches!(
+        mismatch,
+        Err(ComponentAutoSelectMenuBuilderError::DefaultValueTypeMismatch)
+    ));
+
+    let not_auto = ComponentAutoSelectMenuBuilder::default()
+        .menu_type(&ComponentType::Button)
+        .custom_id("button")
+        .build();
+    assert!(matches!(
+        not_auto,
+        Err(ComponentAutoSelectMenuBuilderError::NotAutoSelectMenu)
+    ));
+
+    let range = ComponentAutoSelectMenuBuilder::default()
+        .custom_id("users")
+        .min_values(3)
+        .max_values(5)
+        .build();
+    assert!(range.is_ok());
+
+    let inverted = ComponentAutoSelectMenuBuilder::default()
+        .custom_id("users")
+        .min_values(5)
+        .max_values(3)
+        .build();
+    assert!(matches!(
+        inverted,
+        Err(ComponentAutoSelectMenuBuilderError::MinValuesOverMaxValues)
+    ));
+}
+
+#[test]
+// Typed IDs are (de)serialized as strings and carry their creation time
+fn types_snowflake_id_test() {
+    use crate::types::id::*;
+    use chrono::{TimeZone, Utc};
+
+    let id: UserId = serde_json::from_str("\"175928847299117063\"").unwrap();
+    assert_eq!(id, UserId(175928847299117063));
+    assert_eq!(id, 175928847299117063);
+    assert_eq!(serde_json::to_string(&id).unwrap(), "\"175928847299117063\"");
+
+    // Integers are accepted too
+    let id: GuildId = serde_json::from_str("41771983423143937").unwrap();
+    assert_eq!(id.get(), 41771983423143937);
+
+    assert_eq!(
+        UserId::from(175928847299117063).created_at(),
+        Utc.timestamp_millis_opt(1462015105796).unwrap()
+    );
+}
+
+#[test]
+// Members of the new username system deserialize and use the right display name
+fn types_member_display_name_test() {
+    use crate::types::user::*;
+
+    let mut member: Member = serde_json::from_str(
+        r#"{"user":{"id":"80351110224678912","username":"nelly","global_name":"Nelly","avatar":null},"nick":null,"roles":[],"joined_at":"2015-04-26T06:26:56.936000+00:00","communication_disabled_until":"2015-04-27T06:26:56.936000+00:00","flags":2}"#,
+    )
+    .unwrap();
+
+    assert_eq!(member.display_name(), "Nelly");
+    assert!(!member.is_timed_out());
+    assert_eq!(member.flags, 2);
+
+    member.nick = Some("Nel".to_string());
+    assert_eq!(member.display_name(), "Nel");
+
+    member.user.global_name = None;
+    assert_eq!(member.user.display_name(), "nelly");
+}
+
+#[test]
+// A guild as returned by the API, without deprecated and count fields
+fn types_guild_test() {
+    use crate::types::guild::*;
+
+    let guild: Guild = serde_json::from_str(
+        r#"{"id":"197038439483310086","name":"Discord Testers","icon":"f64c482b807da4f539cff778d174971c","splash":null,"discovery_splash":null,"banner":null,"owner_id":"73193882359173120","afk_channel_id":null,"afk_timeout":300,"verification_level":3,"default_message_notifications":1,"explicit_content_filter":2,"roles":[],"emojis":[{"id":"41771983429993937","name":"LUL","roles":[],"animated":false,"available":true}],"stickers":[{"id":"749054660769218631","name":"Wave","description":"Wave","tags":"wave","format_type":3,"guild_id":"197038439483310086"}],"features":["COMMUNITY","DISCOVERABLE"],"mfa_level":1,"application_id":null,"system_channel_id":null,"system_channel_flags":0,"rules_channel_id":"441688182833020939","vanity_url_code":"discord-testers","description":"The official place to report Discord Bugs!","premium_tier":3,"premium_subscription_count":33,"preferred_locale":"en-US","nsfw_level":0}"#,
+    )
+    .unwrap();
+
+    assert_eq!(guild.verification_level, Some(3));
+    assert_eq!(guild.default_message_notifications, Some(1));
+    assert_eq!(guild.explicit_content_filter, Some(2));
+    assert_eq!(guild.premium_subscription_count, Some(33));
+    assert_eq!(guild.preferred_locale.as_deref(), Some("en-US"));
+    assert_eq!(guild.approximate_member_count, None);
+    assert_eq!(guild.emojis[0].name.as_deref(), Some("LUL"));
+    assert_eq!(
+        guild.stickers.as_ref().unwrap()[0].format_type,
+        StickerFormatType::Lottie
+    );
+
+    let partial = PartialGuild::from(guild);
+    assert_eq!(partial.features, vec!["COMMUNITY", "DISCOVERABLE"]);
+}
+
+#[test]
+// Image URLs pick gif for animated hashes and respect format and size options
+fn types_cdn_test() {
+    use crate::types::cdn::*;
+    use crate::types::id::*;
+
+    let opts = ImageOptions::default();
+    assert_eq!(
+        user_avatar(UserId(80351110224678912), "a_1269e74af4df7417b13759eae50c83dc", opts),
+        "https://cdn.discordapp.com/avatars/80351110224678912/a_1269e74af4df7417b13759eae50c83dc.gif"
+    );
+    assert_eq!(
+        guild_icon(
+            GuildId(41771983423143937),
+            "8342729096ea3675442027381ff50dfe",
+            opts.format(ImageFormat::Gif).size(100)
+        ),
+        "https://cdn.discordapp.com/icons/41771983423143937/8342729096ea3675442027381ff50dfe.png?size=128"
+    );
+    assert_eq!(
+        default_avatar(UserId(80351110224678912), Some("1337")),
+        "https://cdn.discordapp.com/embed/avatars/2.png"
+    );
+    assert_eq!(
+        default_avatar(UserId(80351110224678912), Some("0")),
+        "https://cdn.discordapp.com/embed/avatars/5.png"
+    );
+}
+
+#[test]
+// Mentions, timestamps and emojis are formatted and parsed using Discord's markup
+fn types_format_test() {
+    use crate::types::format::*;
+    use crate::types::id::*;
+    use chrono::{TimeZone, Utc};
+
+    assert_eq!(Mention::Role(RoleId(1234)).to_string(), "<@&1234>");
+    assert_eq!("<@!1234>".parse(), Ok(Mention::User(UserId(1234))));
+    assert_eq!(
+        "</admin ban:99>".parse(),
+        Ok(Mention::SlashCommand {
+            name: "admin ban".to_string(),
+            id: CommandId(99)
+        })
+    );
+    assert_eq!(
+        "<@abc>".parse::<Mention>(),
+        Err(FormatParseError::InvalidId)
+    );
+    assert_eq!(
+        Mention::parse_all("hey <@1> and <#2>, <@&nope> @here <@&3>"),
+        vec![
+            Mention::User(UserId(1)),
+            Mention::Channel(ChannelId(2)),
+            Mention::Here,
+            Mention::Role(RoleId(3))
+        ]
+    );
+
+    let time = Utc.timestamp_opt(1618953630, 0).unwrap();
+    let ts = Timestamp::new(time).style(TimestampStyle::Relative);
+    assert_eq!(ts.to_string(), "<t:1618953630:R>");
+    assert_eq!("<t:1618953630:R>".parse(), Ok(ts));
+    assert_eq!("<t:1618953630>".parse(), Ok(Timestamp::new(time)));
+
+    let emoji: CustomEmoji = "<a:party:42>".parse().unwrap();
+    assert!(emoji.animated);
+    assert_eq!(emoji.to_string(), "<a:party:42>");
+
+    assert_eq!(escape_markdown("**hi**_"), "\\*\\*hi\\*\\*\\_");
+    assert_eq!(code_block(Some("rs"), "a```b"), "```rs\na`\u{200B}``b\n```");
+}
+
+#[cfg(feature = "builder")]
+#[test]
+// Layout components are nested and validated by their builders
+fn types_components_v2_builder_test() {
+    use crate::types::components::*;
+    use crate::Builder;
+
+    let section = ComponentSectionBuilder::default()
+        .add_text(ComponentTextDisplay::new("**Hello**"))
+        .thumbnail_accessory(ComponentThumbnail::new("https://example.com/a.png"))
+        .build()
+        .unwrap();
+    let container = ComponentContainerBuilder::default()
+        .accent_color(0x5865F2)
+        .add_component(section)
+        .add_component(ComponentSeparator::default().spacing(SeparatorSpacing::Large))
+        .add_component(ComponentFile::attachment("log.txt"))
+        .build()
+        .unwrap();
+
+    let json = serde_json::to_value(&container).unwrap();
+    assert_eq!(json["type"], 17);
+    assert_eq!(json["components"][0]["accessory"]["type"], 11);
+    assert_eq!(json["components"][0]["components"][0]["content"], "**Hello**");
+    assert_eq!(json["components"][1]["spacing"], 2);
+    assert_eq!(json["components"][2]["file"]["url"], "attachment://log.txt");
+
+    let parsed: MessageComponent = serde_json::from_value(json).unwrap();
+    assert_eq!(parsed, container);
+
+    assert_eq!(
+        ComponentSectionBuilder::default()
+            .add_text(ComponentTextDisplay::new("No accessory"))
+            .build(),
+        Err(ComponentLayoutBuilderError::SectionWithoutAccessory)
+    );
+    assert_eq!(
+        ComponentMediaGalleryBuilder::default().build(),
+        Err(ComponentLayoutBuilderError::MediaGalleryItemCount)
+    );
+    assert_eq!(
+        ComponentContainerBuilder::default()
+            .add_component(ComponentTextDisplay::new("Text"))
+            .add_component(container)
+            .build(),
+        Err(ComponentLayoutBuilderError::InvalidContainerChild(1))
+    );
+}
+
+#[cfg(feature = "builder")]
+#[test]
+// Allowed mentions serialize like Discord expects and conflicting settings are rejected
+fn types_allowed_mentions_test() {
+    use crate::types::interaction::*;
+    use crate::Builder;
+
+    let am = AllowedMentionsBuilder::default()
+        .all_users(true)
+        .add_role(41771983423143936)
+        .replied_user(true)
+        .build()
+        .unwrap();
+    let json = serde_json::to_value(&am).unwrap();
+    assert_eq!(json["parse"], serde_json::json!(["users"]));
+    assert_eq!(json["roles"], serde_json::json!(["41771983423143936"]));
+    assert_eq!(json["replied_user"], true);
+
+    assert_eq!(
+        serde_json::to_value(AllowedMentions::none()).unwrap()["parse"],
+        serde_json::json!([])
+    );
+    assert_eq!(
+        AllowedMentionsBuilder::default()
+            .all_users(true)
+            .add_user(80351110224678912)
+            .build(),
+        Err(AllowedMentionsBuilderError::UsersConflict)
+    );
+    assert_eq!(
+        AllowedMentionsBuilder::default()
+            .everyone(true)
+            .everyone(false)
+            .build()
+            .unwrap(),
+        AllowedMentions::none()
+    );
+}
+
+#[test]
+// Message flags are (de)serialized as an integer, unknown bits are kept
+fn types_message_flags_test() {
+    use crate::types::interaction::MessageFlags;
+
+    let flags = MessageFlags::EPHEMERAL | MessageFlags::IS_COMPONENTS_V2;
+    assert_eq!(serde_json::to_string(&flags).unwrap(), "32832");
+
+    let flags: MessageFlags = serde_json::from_str("4100").unwrap();
+    assert_eq!(
+        flags,
+        MessageFlags::SUPPRESS_EMBEDS | MessageFlags::SUPPRESS_NOTIFICATIONS
+    );
+
+    let flags: MessageFlags = serde_json::from_str("1048576").unwrap();
+    assert_eq!(flags.bits(), 1 << 20);
+}
+
+#[cfg(feature = "handler")]
+#[test]
+// Flags can be cleared again, and only response flags are accepted
+fn types_response_flags_test() {
+    use crate::types::interaction::*;
+    use crate::Builder;
+
+    let res = InteractionResponseBuilder::default()
+        .content("Hi")
+        .is_ephemeral(true)
+        .suppress_embeds(true)
+        .is_ephemeral(false)
+        .build()
+        .unwrap();
+    assert_eq!(
+        serde_json::to_value(&res).unwrap()["data"]["flags"],
+        serde_json::json!(4)
+    );
+
+    let res = InteractionResponseBuilder::default()
+        .content("Hi")
+        .flags(MessageFlags::EPHEMERAL | MessageFlags::URGENT)
+        .build();
+    assert!(matches!(
+        res,
+        Err(ResponseError::UnsupportedFlags(f)) if f == MessageFlags::URGENT
+    ));
+
+    // Only flags that can be edited are kept when the response edits a message
+    let res = InteractionResponseBuilder::default()
+        .content("Hi")
+        .is_ephemeral(true)
+        .suppress_embeds(true)
+        .build()
+        .unwrap();
+    let msg = serde_json::to_value(WebhookMessage::from(res)).unwrap();
+    assert_eq!(msg["flags"], serde_json::json!(4));
+
+    let res = InteractionResponseBuilder::default()
+        .content("Hi")
+        .is_ephemeral(true)
+        .build()
+        .unwrap();
+    let msg = serde_json::to_value(WebhookMessage::from(res)).unwrap();
+    assert!(msg.get("flags").is_none());
+}
+
+#[cfg(feature = "handler")]
+#[test]
+// Responses over Discord's limits are rejected by the builder
+fn types_response_limits_test() {
+    use crate::types::components::*;
+    use crate::types::embed::EmbedBuilder;
+    use crate::types::interaction::*;
+    use crate::Builder;
+
+    let button = |id: &str| {
+        ComponentButtonBuilder::default()
+            .label(id)
+            .custom_id(id)
+            .style(&ComponentButtonStyle::Primary)
+            .build()
+            .unwrap()
+    };
+    let row = || {
+        ComponentRowBuilder::default()
+            .add_button(button("a"))
+            .build()
+            .unwrap()
+    };
+
+    let res = InteractionResponseBuilder::default()
+        .content("a".repeat(2000))
+        .build();
+    assert!(res.is_ok());
+    let res = InteractionResponseBuilder::default()
+        .content("a".repeat(2001))
+        .build();
+    assert!(matches!(res, Err(ResponseError::ContentTooLong)));
+
+    let embed = EmbedBuilder::default().title("Embed").build().unwrap();
+    let mut builder = InteractionResponseBuilder::default();
+    for _ in 0..10 {
+        builder = builder.add_embed(&embed);
+    }
+    assert!(builder.clone().build().is_ok());
+    let res = builder.add_embed(&embed).build();
+    assert!(matches!(res, Err(ResponseError::TooManyEmbeds)));
+
+    // Every embed is within the limits, but together they exceed 6000 characters
+    let long = EmbedBuilder::default()
+        .description("a".repeat(3000))
+        .build()
+        .unwrap();
+    let builder = InteractionResponseBuilder::default()
+        .add_embed(&long)
+        .add_embed(&long);
+    assert!(builder.clone().build().is_ok());
+    let res = builder.add_embed(&embed).build();
+    assert!(matches!(res, Err(ResponseError::EmbedsTooLong)));
+
+    let mut builder = InteractionResponseBuilder::default();
+    for _ in 0..5 {
+        builder = builder.add_component_row(row());
+    }
+    assert!(builder.clone().build().is_ok());
+    let res = builder.add_component_row(row()).build();
+    assert!(matches!(res, Err(ResponseError::TooManyActionRows)));
+
+    let mut full_row = ComponentRowBuilder::default();
+    for id in &["1", "2", "3", "4", "5"] {
+        full_row = full_row.add_button(button(id));
+    }
+    let res = InteractionResponseBuilder::default()
+        .add_component_row(full_row.clone().build().unwrap())
+        .build();
+    assert!(res.is_ok());
+    let res = InteractionResponseBuilder::default()
+        .add_component_row(full_row.add_button(button("6")).build().unwrap())
+        .build();
+    assert!(matches!(res, Err(ResponseError::TooManyButtons(0))));
+
+    let select = || {
+        ComponentSelectMenuBuilder::default()
+            .custom_id("select")
+            .add_option(ComponentSelectOption::default().label("A").value("a"))
+            .build()
+            .unwrap()
+    };
+    let res = InteractionResponseBuilder::default()
+        .add_component_row(row())
+        .add_component_row(
+            ComponentRowBuilder::default()
+                .add_select_menu(select())
+                .build()
+                .unwrap(),
+        )
+        .build();
+    assert!(res.is_ok());
+    let res = InteractionResponseBuilder::default()
+        .add_component_row(
+            ComponentRowBuilder::default()
+                .add_select_menu(select())
+                .add_select_menu(select())
+                .build()
+                .unwrap(),
+        )
+        .build();
+    assert!(matches!(res, Err(ResponseError::InvalidSelectMenuRow(0))));
+    let res = InteractionResponseBuilder::default()
+        .add_component_row(row())
+        .add_component_row(
+            ComponentRowBuilder::default()
+                .add_select_menu(select())
+                .add_button(button("b"))
+                .build()
+                .unwrap(),
+        )
+        .build();
+    assert!(matches!(res, Err(ResponseError::InvalidSelectMenuRow(1))));
+}
+
+#[cfg(feature = "handler-core")]
+#[test]
+// Webhook messages are held to the same limits as responses
+fn types_webhook_message_limits_test() {
+    use crate::types::embed::EmbedBuilder;
+    use crate::types::interaction::*;
+    use crate::Builder;
+
+    let embed = EmbedBuilder::default().title("Embed").build().unwrap();
+    let mut message = WebhookMessage::default();
+    for _ in 0..10 {
+        message = message.add_embed(embed.clone());
+    }
+    assert!(message.validate().is_ok());
+    // The 11th embed is kept, so the message is rejected instead of sent without it
+    let message = message.add_embed(embed);
+    assert_eq!(message.embeds.as_ref().unwrap().len(), 11);
+    assert_eq!(message.validate(), Err(ResponseError::TooManyEmbeds));
+
+    let long = EmbedBuilder::default()
+        .description("a".repeat(3000))
+        .build()
+        .unwrap();
+    let message = WebhookMessage::default()
+        .add_embed(long.clone())
+        .add_embed(long);
+    assert!(message.validate().is_ok());
+    let message = message.add_embed(EmbedBuilder::default().title("Embed").build().unwrap());
+    assert_eq!(message.validate(), Err(ResponseError::EmbedsTooLong));
+
+    let message = WebhookMessage::default().content("a".repeat(2001));
+    assert_eq!(message.validate(), Err(ResponseError::ContentTooLong));
+}
+
+#[cfg(feature = "handler")]
+#[test]
+// Response types are checked against the type of interaction they respond to
+fn types_response_type_test() {
+    use crate::types::interaction::*;
+    use crate::Builder;
+
+    let interaction = |t: u8| -> Context {
+        let payload = format!(
+            "{{\"type\":{},\"token\":\"awQabcabc\",\"id\":\"786008729715212338\"}}",
+            t
+        );
+        Context::new(
+            reqwest::Client::new(),
+            serde_json::from_str(&payload).unwrap(),
+        )
+    };
+
+    let command = interaction(2);
+    let res = command
+        .respond()
+        .respond_type(InteractionResponseType::UpdateMessage)
+        .content("Hi")
+        .build();
+    assert!(matches!(
+        res,
+        Err(ResponseError::InvalidResponseType {
+            response: InteractionResponseType::UpdateMessage,
+            interaction: InteractionType::ApplicationCommand,
+        })
+    ));
+    let res = command
+        .respond()
+        .respond_type(InteractionResponseType::DefferedUpdateMessage)
+        .build();
+    assert!(res.is_err());
+    assert!(command.respond().content("Hi").build().is_ok());
+
+    // Components update their message by default, and can also reply with a new one
+    let component = interaction(3);
+    let res = component.respond().content("Hi").build().unwrap();
+    assert_eq!(res.r#type, InteractionResponseType::UpdateMessage);
+    let res = component
+        .respond()
+        .respond_type(InteractionResponseType::ChannelMessageWithSource)
+        .content("Hi")
+        .build();
+    assert!(res.is_ok());
+    let res = component
+        .respond()
+        .respond_type(InteractionResponseType::Pong)
+        .build();
+    assert!(res.is_err());
+
+    // Builders that are not tied to an interaction accept any type
+    let res = InteractionResponseBuilder::default()
+        .respond_type(InteractionResponseType::UpdateMessage)
+        .content("Hi")
+        .build();
+    assert!(res.is_ok());
+}
+
 /*-------------------------------
 Discord Interactions API tests (endpoint: /api/discord/interactions)
 */
@@ -79,15 +796,15 @@ Discord Interactions API tests (endpoint: /api/discord/interactions)
 macro_rules! interaction_app_init {
     ($ih: ident) => {
 
-        test::init_service(App::new().app_data($ih.clone()).route(
+        actix_test::init_service(App::new().app_data($ih.clone()).route(
             "/api/discord/interactions",
             web::post().to(
-                |data: web::Data<Mutex<InteractionHandler>>, req: HttpRequest, body: String| async move {
-                    data.lock().unwrap().interaction(req, body).await
+                |data: web::Data<InteractionHandler>, req: HttpRequest, body: String| async move {
+                    data.interaction(req, body).await
                 },
             ),
         ))
-        .await;
+        .await
     };
 }
 #[cfg(all(feature = "handler", not(feature = "extended-handler")))]
@@ -111,15 +828,15 @@ macro_rules! init_handler {
 async fn interactions_no_content_type_header_test() {
     let ih = init_handler!();
 
-    let data = web::Data::new(Mutex::new(ih));
-    let mut app = interaction_app_init!(data);
+    let data = web::Data::new(ih);
+    let app = interaction_app_init!(data);
 
-    let req = test::TestRequest::post()
+    let req = actix_test::TestRequest::post()
         .uri("/api/discord/interactions")
         .set_payload("This is some malformed text { the system : can't really handle }")
         .to_request();
 
-    let res: types::MessageError = test::read_response_json(&mut app, req).await;
+    let res: types::MessageError = actix_test::call_and_read_body_json(&app, req).await;
 
     assert_eq!(res.message, "Bad Content-Type");
 }
@@ -131,16 +848,16 @@ async fn interactions_no_content_type_header_test() {
 async fn interactions_bad_content_type_header_test() {
     let ih = init_handler!();
 
-    let data = web::Data::new(Mutex::new(ih));
-    let mut app = interaction_app_init!(data);
+    let data = web::Data::new(ih);
+    let app = interaction_app_init!(data);
 
-    let req = test::TestRequest::post()
+    let req = actix_test::TestRequest::post()
         .uri("/api/discord/interactions")
-        .header("Content-Type", "plain/text")
+        .insert_header(("Content-Type", "plain/text"))
         .set_payload("This is some malformed text { the system : can't really handle }")
         .to_request();
 
-    let res: types::MessageError = test::read_response_json(&mut app, req).await;
+    let res: types::MessageError = actix_test::call_and_read_body_json(&app, req).await;
 
     assert_eq!(res.message, "Bad Content-Type");
 }
@@ -152,17 +869,17 @@ async fn interactions_bad_content_type_header_test() {
 async fn interactions_no_signature_header_test() {
     let ih = init_handler!();
 
-    let data = web::Data::new(Mutex::new(ih));
-    let mut app = interaction_app_init!(data);
+    let data = web::Data::new(ih);
+    let app = interaction_app_init!(data);
 
-    let req = test::TestRequest::post()
+    let req = actix_test::TestRequest::post()
         .uri("/api/discord/interactions")
-        .header("Content-Type", "application/json")
-        .header("X-Signature-Timestamp", "1229349")
+        .insert_header(("Content-Type", "application/json"))
+        .insert_header(("X-Signature-Timestamp", "1229349"))
         .set_payload("This is some malformed text { the system : can't really handle }")
         .to_request();
 
-    let res: types::MessageError = test::read_response_json(&mut app, req).await;
+    let res: types::MessageError = actix_test::call_and_read_body_json(&app, req).await;
 
     assert_eq!(res.message, "Bad signature data");
 }
@@ -174,17 +891,17 @@ async fn interactions_no_signature_header_test() {
 async fn interactions_no_timestamp_header_test() {
     let ih = init_handler!();
 
-    let data = web::Data::new(Mutex::new(ih));
-    let mut app = interaction_app_init!(data);
+    let data = web::Data::new(ih);
+    let app = interaction_app_init!(data);
 
-    let req = test::TestRequest::post()
+    let req = actix_test::TestRequest::post()
         .uri("/api/discord/interactions")
-        .header("Content-Type", "application/json")
-        .header("X-Signature-Ed25519", "69696969696969696696969696969696969696969696969696969696969696969696969696969696969696969696969696969696969696969696969696696969")
+        .insert_header(("Content-Type", "application/json"))
+        .insert_header(("X-Signature-Ed25519", "69696969696969696696969696969696969696969696969696969696969696969696969696969696969696969696969696969696969696969696969696696969"))
         .set_payload("This is some malformed text { the system : can't really handle }")
         .to_request();
 
-    let res: types::MessageError = test::read_response_json(&mut app, req).await;
+    let res: types::MessageError = actix_test::call_and_read_body_json(&app, req).await;
 
     assert_eq!(res.message, "Bad signature data");
 }
@@ -196,20 +913,20 @@ async fn interactions_no_timestamp_header_test() {
 async fn interactions_bad_signature_length_short_test() {
     let ih = init_handler!();
 
-    let data = web::Data::new(Mutex::new(ih));
-    let mut app = interaction_app_init!(data);
+    let data = web::Data::new(ih);
+    let app = interaction_app_init!(data);
 
-    let req = test::TestRequest::post()
+    let req = actix_test::TestRequest::post()
         .uri("/api/discord/interactions")
-        .header("Content-Type", "application/json")
-        .header(
+        .insert_header(("Content-Type", "application/json"))
+        .insert_header((
             "X-Signature-Ed25519",
             "69696969696969696696969696969696969696969696969696969696969696969",
-        )
+        ))
         .set_payload("This is some malformed text { the system : can't really handle }")
         .to_request();
 
-    let res: types::MessageError = test::read_response_json(&mut app, req).await;
+    let res: types::MessageError = actix_test::call_and_read_body_json(&app, req).await;
 
     assert_eq!(res.message, "Bad signature data");
 }
@@ -221,17 +938,17 @@ async fn interactions_bad_signature_length_short_test() {
 async fn interactions_bad_signature_length_too_long_test() {
     let ih = init_handler!();
 
-    let data = web::Data::new(Mutex::new(ih));
-    let mut app = interaction_app_init!(data);
+    let data = web::Data::new(ih);
+    let app = interaction_app_init!(data);
 
-    let req = test::TestRequest::post()
+    let req = actix_test::TestRequest::post()
         .uri("/api/discord/interactions")
-        .header("Content-Type", "application/json")
-        .header("X-Signature-Ed25519", "6969696969696969669696969696969696969696969696969696969696969696969696969696969696969696969696969696969696969696969696969669696969696969696969696696969696969696969696969696969696969696969")
+        .insert_header(("Content-Type", "application/json"))
+        .insert_header(("X-Signature-Ed25519", "6969696969696969669696969696969696969696969696969696969696969696969696969696969696969696969696969696969696969696969696969669696969696969696969696696969696969696969696969696969696969696969"))
         .set_payload("This is some malformed text { the system : can't really handle }")
         .to_request();
 
-    let res: types::MessageError = test::read_response_json(&mut app, req).await;
+    let res: types::MessageError = actix_test::call_and_read_body_json(&app, req).await;
 
     assert_eq!(res.message, "Bad signature data");
 }
@@ -243,19 +960,19 @@ async fn interactions_bad_signature_length_too_long_test() {
 async fn interactions_ping_test() {
     let ih = init_handler!();
 
-    let data = web::Data::new(Mutex::new(ih));
-    let mut app = interaction_app_init!(data);
+    let data = web::Data::new(ih);
+    let app = interaction_app_init!(data);
 
-    let req = test::TestRequest::post()
+    let req = actix_test::TestRequest::post()
         .uri("/api/discord/interactions")
-        .header("Content-Type", "application/json")
-        .header("X-Signature-Ed25519", "c41278a0cf22bf8f3061756063cd7ef548a3df23d0ffc5496209aa0ad4d9593343801bf11e099f41bca1afcac2c70734eebafede3dec7aac1caa5d8fade5af0c")
-        .header("X-Signature-Timestamp", "1616343571")
+        .insert_header(("Content-Type", "application/json"))
+        .insert_header(("X-Signature-Ed25519", "c41278a0cf22bf8f3061756063cd7ef548a3df23d0ffc5496209aa0ad4d9593343801bf11e099f41bca1afcac2c70734eebafede3dec7aac1caa5d8fade5af0c"))
+        .insert_header(("X-Signature-Timestamp", "1616343571"))
         .set_payload("{\"type\" : 1}")
         .to_request();
 
     let res: types::interaction::InteractionResponse =
-        test::read_response_json(&mut app, req).await;
+        actix_test::call_and_read_body_json(&app, req).await;
 
     assert_eq!(
         res.r#type,
@@ -270,32 +987,32 @@ async fn interactions_ping_test() {
 async fn interactions_bad_body_test() {
     let ih = init_handler!();
 
-    let data = web::Data::new(Mutex::new(ih));
-    let mut app = interaction_app_init!(data);
+    let data = web::Data::new(ih);
+    let app = interaction_app_init!(data);
 
-    let req = test::TestRequest::post()
+    let req = actix_test::TestRequest::post()
         .uri("/api/discord/interactions")
-        .header("Content-Type", "application/json")
-        .header("X-Signature-Ed25519", "51c5defa19cc2471a361c00c87a7f380d9e9d6cd21f05b65d3c223aac0b7d258277a09d0a016108e0be1338d985ed4ce0dae55e5ac93db5957a37ce31d007505")
-        .header("X-Signature-Timestamp", "1616343571")
+        .insert_header(("Content-Type", "application/json"))
+        .insert_header(("X-Signature-Ed25519", "51c5defa19cc2471a361c00c87a7f380d9e9d6cd21f05b65d3c223aac0b7d258277a09d0a016108e0be1338d985ed4ce0dae55e5ac93db5957a37ce31d007505"))
+        .insert_header(("X-Signature-Timestamp", "1616343571"))
         .set_payload("this is some malformed {\"data\" : cant handle}")
         .to_request();
 
-    let res = test::call_service(&mut app, req).await;
+    let res = actix_test::call_service(&app, req).await;
 
-    assert_eq!(res.status(), http::StatusCode::BAD_REQUEST);
+    assert_eq!(res.status(), actix_web::http::StatusCode::BAD_REQUEST);
 }
 #[cfg(feature = "handler")]
 #[allow(unused_must_use)]
 #[slash_command]
 async fn normal_handle_test(ctx: Context) -> InteractionResponse {
-    return ctx.respond().content("TEST").finish();
+    return ctx.respond().content("TEST").build().unwrap();
 }
 #[cfg(feature = "handler")]
 #[allow(unused_must_use)]
 #[slash_command]
 async fn normal_handle_value_test(ctx: Context) -> InteractionResponse {
-    let response = ctx.respond().content("TEST").finish();
+    let response = ctx.respond().content("TEST").build().unwrap();
     return response;
 }
 #[cfg(feature = "handler")]
@@ -304,7 +1021,8 @@ async fn normal_handle_value_test(ctx: Context) -> InteractionResponse {
 async fn normal_handle_direct_test(_ctx: Context) -> InteractionResponse {
     return InteractionResponseBuilder::default()
         .content("TEST")
-        .finish();
+        .build()
+        .unwrap();
 }
 #[cfg(feature = "handler")]
 #[actix_rt::test]
@@ -313,23 +1031,24 @@ async fn interactions_normal_handle_test() {
 
     ih.add_global_command("test", normal_handle_test);
 
-    let data = web::Data::new(Mutex::new(ih));
-    let mut app = interaction_app_init!(data);
+    let data = web::Data::new(ih);
+    let app = interaction_app_init!(data);
 
-    let req = test::TestRequest::post()
+    let req = actix_test::TestRequest::post()
         .uri("/api/discord/interactions")
-        .header("Content-Type", "application/json")
-        .header("X-Signature-Ed25519", "a27ed2fd0e91da58667bec63d14406e5274a0427edad9530b7d95e9d2b0fc4ee17f74e8a6bd3acd6623a05f1bde9e598fa37f3eedfe479da0a00da7827595e0b")
-        .header("X-Signature-Timestamp", "1616343571")
+        .insert_header(("Content-Type", "application/json"))
+        .insert_header(("X-Signature-Ed25519", "a27ed2fd0e91da58667bec63d14406e5274a0427edad9530b7d95e9d2b0fc4ee17f74e8a6bd3acd6623a05f1bde9e598fa37f3eedfe479da0a00da7827595e0b"))
+        .insert_header(("X-Signature-Timestamp", "1616343571"))
         .set_payload("{\"type\":2,\"token\":\"awQabcabc\",\"member\":{\"user\":{\"id\":\"317209107000066050\",\"username\":\"C0der\",\"avatar\":\"a_d5efa99b3eeaa7dd43acca82f5692432\",\"discriminator\":\"1337\",\"public_flags\":131141},\"roles\":[],\"premium_since\":null,\"permissions\":\"2147483647\",\"pending\":false,\"nick\":null,\"mute\":false,\"joined_at\":\"2017-03-13T19:19:14.040000+00:00\",\"is_pending\":false,\"deaf\":false},\"id\":\"786008729715212338\",\"guild_id\":\"290926798626357999\",\"data\":{\"name\":\"test\",\"id\":\"771825006014889984\"},\"channel_id\":\"645027906669510667\"}")
         .to_request();
 
     let res: types::interaction::InteractionResponse =
-        test::read_response_json(&mut app, req).await;
+        actix_test::call_and_read_body_json(&app, req).await;
 
     let expected_data = InteractionResponseBuilder::default()
         .content("TEST")
-        .finish();
+        .build()
+        .unwrap();
 
     //let expected_res = HttpResponse::build(StatusCode::OK).json(expected_data);
 
@@ -342,23 +1061,24 @@ async fn interactions_normal_from_value_handle_test() {
 
     ih.add_global_command("test", normal_handle_direct_test);
 
-    let data = web::Data::new(Mutex::new(ih));
-    let mut app = interaction_app_init!(data);
+    let data = web::Data::new(ih);
+    let app = interaction_app_init!(data);
 
-    let req = test::TestRequest::post()
+    let req = actix_test::TestRequest::post()
         .uri("/api/discord/interactions")
-        .header("Content-Type", "application/json")
-        .header("X-Signature-Ed25519", "a27ed2fd0e91da58667bec63d14406e5274a0427edad9530b7d95e9d2b0fc4ee17f74e8a6bd3acd6623a05f1bde9e598fa37f3eedfe479da0a00da7827595e0b")
-        .header("X-Signature-Timestamp", "1616343571")
+        .insert_header(("Content-Type", "application/json"))
+        .insert_header(("X-Signature-Ed25519", "a27ed2fd0e91da58667bec63d14406e5274a0427edad9530b7d95e9d2b0fc4ee17f74e8a6bd3acd6623a05f1bde9e598fa37f3eedfe479da0a00da7827595e0b"))
+        .insert_header(("X-Signature-Timestamp", "1616343571"))
         .set_payload("{\"type\":2,\"token\":\"awQabcabc\",\"member\":{\"user\":{\"id\":\"317209107000066050\",\"username\":\"C0der\",\"avatar\":\"a_d5efa99b3eeaa7dd43acca82f5692432\",\"discriminator\":\"1337\",\"public_flags\":131141},\"roles\":[],\"premium_since\":null,\"permissions\":\"2147483647\",\"pending\":false,\"nick\":null,\"mute\":false,\"joined_at\":\"2017-03-13T19:19:14.040000+00:00\",\"is_pending\":false,\"deaf\":false},\"id\":\"786008729715212338\",\"guild_id\":\"290926798626357999\",\"data\":{\"name\":\"test\",\"id\":\"771825006014889984\"},\"channel_id\":\"645027906669510667\"}")
         .to_request();
 
     let res: types::interaction::InteractionResponse =
-        test::read_response_json(&mut app, req).await;
+        actix_test::call_and_read_body_json(&app, req).await;
 
     let expected_data = InteractionResponseBuilder::default()
         .content("TEST")
-        .finish();
+        .build()
+        .unwrap();
 
     //let expected_res = HttpResponse::build(StatusCode::OK).json(expected_data);
 
@@ -371,42 +1091,100 @@ async fn interactions_normal_from_direct_call_handle_test() {
 
     ih.add_global_command("test", normal_handle_value_test);
 
-    let data = web::Data::new(Mutex::new(ih));
-    let mut app = interaction_app_init!(data);
+    let data = web::Data::new(ih);
+    let app = interaction_app_init!(data);
 
-    let req = test::TestRequest::post()
+    let req = actix_test::TestRequest::post()
         .uri("/api/discord/interactions")
-        .header("Content-Type", "application/json")
-        .header("X-Signature-Ed25519", "a27ed2fd0e91da58667bec63d14406e5274a0427edad9530b7d95e9d2b0fc4ee17f74e8a6bd3acd6623a05f1bde9e598fa37f3eedfe479da0a00da7827595e0b")
-        .header("X-Signature-Timestamp", "1616343571")
+        .insert_header(("Content-Type", "application/json"))
+        .insert_header(("X-Signature-Ed25519", "a27ed2fd0e91da58667bec63d14406e5274a0427edad9530b7d95e9d2b0fc4ee17f74e8a6bd3acd6623a05f1bde9e598fa37f3eedfe479da0a00da7827595e0b"))
+        .insert_header(("X-Signature-Timestamp", "1616343571"))
         .set_payload("{\"type\":2,\"token\":\"awQabcabc\",\"member\":{\"user\":{\"id\":\"317209107000066050\",\"username\":\"C0der\",\"avatar\":\"a_d5efa99b3eeaa7dd43acca82f5692432\",\"discriminator\":\"1337\",\"public_flags\":131141},\"roles\":[],\"premium_since\":null,\"permissions\":\"2147483647\",\"pending\":false,\"nick\":null,\"mute\":false,\"joined_at\":\"2017-03-13T19:19:14.040000+00:00\",\"is_pending\":false,\"deaf\":false},\"id\":\"786008729715212338\",\"guild_id\":\"290926798626357999\",\"data\":{\"name\":\"test\",\"id\":\"771825006014889984\"},\"channel_id\":\"645027906669510667\"}")
         .to_request();
 
     let res: types::interaction::InteractionResponse =
-        test::read_response_json(&mut app, req).await;
+        actix_test::call_and_read_body_json(&app, req).await;
 
     let expected_data = InteractionResponseBuilder::default()
         .content("TEST")
-        .finish();
+        .build()
+        .unwrap();
 
     //let expected_res = HttpResponse::build(StatusCode::OK).json(expected_data);
 
     assert_eq!(res, expected_data);
 }
 
+#[cfg(feature = "extended-handler")]
+#[actix_rt::test]
+// Local guild handles are routed by command ID, so registering one requires an ID
+async fn interactions_local_guild_handle_test() {
+    use crate::handler::ManipulationScope;
+    use crate::types::application::ApplicationCommand;
+
+    let ih = init_handler!();
+
+    let cmd: ApplicationCommand =
+        serde_json::from_str("{\"name\":\"test\",\"description\":\"Test\"}").unwrap();
+    let res = ih
+        .register_guild_handle(
+            290926798626357999,
+            &cmd,
+            normal_handle_test,
+            &ManipulationScope::Local,
+        )
+        .await;
+    assert!(res.is_err());
+
+    let cmd: ApplicationCommand = serde_json::from_str(
+        "{\"id\":\"771825006014889984\",\"name\":\"test\",\"description\":\"Test\"}",
+    )
+    .unwrap();
+    let res = ih
+        .register_guild_handle(
+            290926798626357999,
+            &cmd,
+            normal_handle_test,
+            &ManipulationScope::Local,
+        )
+        .await;
+    assert!(res.is_ok());
+
+    let data = web::Data::new(ih);
+    let app = interaction_app_init!(data);
+
+    let req = actix_test::TestRequest::post()
+        .uri("/api/discord/interactions")
+        .insert_header(("Content-Type", "application/json"))
+        .insert_header(("X-Signature-Ed25519", "a27ed2fd0e91da58667bec63d14406e5274a0427edad9530b7d95e9d2b0fc4ee17f74e8a6bd3acd6623a05f1bde9e598fa37f3eedfe479da0a00da7827595e0b"))
+        .insert_header(("X-Signature-Timestamp", "1616343571"))
+        .set_payload("{\"type\":2,\"token\":\"awQabcabc\",\"member\":{\"user\":{\"id\":\"317209107000066050\",\"username\":\"C0der\",\"avatar\":\"a_d5efa99b3eeaa7dd43acca82f5692432\",\"discriminator\":\"1337\",\"public_flags\":131141},\"roles\":[],\"premium_since\":null,\"permissions\":\"2147483647\",\"pending\":false,\"nick\":null,\"mute\":false,\"joined_at\":\"2017-03-13T19:19:14.040000+00:00\",\"is_pending\":false,\"deaf\":false},\"id\":\"786008729715212338\",\"guild_id\":\"290926798626357999\",\"data\":{\"name\":\"test\",\"id\":\"771825006014889984\"},\"channel_id\":\"645027906669510667\"}")
+        .to_request();
+
+    let res: types::interaction::InteractionResponse =
+        actix_test::call_and_read_body_json(&app, req).await;
+
+    let expected_data = InteractionResponseBuilder::default()
+        .content("TEST")
+        .build()
+        .unwrap();
+
+    assert_eq!(res, expected_data);
+}
+
 #[cfg(feature = "handler")]
 use crate::types::interaction::WebhookMessage;
 #[cfg(feature = "handler")]
 #[slash_command_test]
 #[defer]
 async fn deffered_handle_test(ctx: Context) -> InteractionResponse {
-    return ctx.respond().content("TEST").finish();
+    return ctx.respond().content("TEST").build().unwrap();
 }
 #[cfg(feature = "handler")]
 #[slash_command_test]
 #[defer]
 async fn deffered_handle_value_test(ctx: Context) -> InteractionResponse {
-    let response = ctx.respond().content("TEST").finish();
+    let response = ctx.respond().content("TEST").build().unwrap();
     return response;
 }
 #[cfg(feature = "handler")]
@@ -415,7 +1193,8 @@ async fn deffered_handle_value_test(ctx: Context) -> InteractionResponse {
 async fn deffered_handle_direct_test(_ctx: Context) -> InteractionResponse {
     return InteractionResponseBuilder::default()
         .content("TEST")
-        .finish();
+        .build()
+        .unwrap();
 }
 #[cfg(feature = "handler")]
 #[actix_rt::test]
@@ -424,19 +1203,19 @@ async fn interactions_deffered_handle_test() {
 
     ih.add_global_command("test", deffered_handle_test);
 
-    let data = web::Data::new(Mutex::new(ih));
-    let mut app = interaction_app_init!(data);
+    let data = web::Data::new(ih);
+    let app = interaction_app_init!(data);
 
-    let req = test::TestRequest::post()
+    let req = actix_test::TestRequest::post()
         .uri("/api/discord/interactions")
-        .header("Content-Type", "application/json")
-        .header("X-Signature-Ed25519", "a27ed2fd0e91da58667bec63d14406e5274a0427edad9530b7d95e9d2b0fc4ee17f74e8a6bd3acd6623a05f1bde9e598fa37f3eedfe479da0a00da7827595e0b")
-        .header("X-Signature-Timestamp", "1616343571")
+        .insert_header(("Content-Type", "application/json"))
+        .insert_header(("X-Signature-Ed25519", "a27ed2fd0e91da58667bec63d14406e5274a0427edad9530b7d95e9d2b0fc4ee17f74e8a6bd3acd6623a05f1bde9e598fa37f3eedfe479da0a00da7827595e0b"))
+        .insert_header(("X-Signature-Timestamp", "1616343571"))
         .set_payload("{\"type\":2,\"token\":\"awQabcabc\",\"member\":{\"user\":{\"id\":\"317209107000066050\",\"username\":\"C0der\",\"avatar\":\"a_d5efa99b3eeaa7dd43acca82f5692432\",\"discriminator\":\"1337\",\"public_flags\":131141},\"roles\":[],\"premium_since\":null,\"permissions\":\"2147483647\",\"pending\":false,\"nick\":null,\"mute\":false,\"joined_at\":\"2017-03-13T19:19:14.040000+00:00\",\"is_pending\":false,\"deaf\":false},\"id\":\"786008729715212338\",\"guild_id\":\"290926798626357999\",\"data\":{\"name\":\"test\",\"id\":\"771825006014889984\"},\"channel_id\":\"645027906669510667\"}")
         .to_request();
 
     let res: types::interaction::InteractionResponse =
-        test::read_response_json(&mut app, req).await;
+        actix_test::call_and_read_body_json(&app, req).await;
 
     let expected_data = InteractionResponse {
         r#type: InteractionResponseType::DefferedChannelMessageWithSource,
@@ -453,19 +1232,19 @@ async fn interactions_deffered_from_value_handle_test() {
     let mut ih = init_handler!();
 
     ih.add_global_command("test", deffered_handle_value_test);
-    let data = web::Data::new(Mutex::new(ih));
-    let mut app = interaction_app_init!(data);
+    let data = web::Data::new(ih);
+    let app = interaction_app_init!(data);
 
-    let req = test::TestRequest::post()
+    let req = actix_test::TestRequest::post()
         .uri("/api/discord/interactions")
-        .header("Content-Type", "application/json")
-        .header("X-Signature-Ed25519", "a27ed2fd0e91da58667bec63d14406e5274a0427edad9530b7d95e9d2b0fc4ee17f74e8a6bd3acd6623a05f1bde9e598fa37f3eedfe479da0a00da7827595e0b")
-        .header("X-Signature-Timestamp", "1616343571")
+        .insert_header(("Content-Type", "application/json"))
+        .insert_header(("X-Signature-Ed25519", "a27ed2fd0e91da58667bec63d14406e5274a0427edad9530b7d95e9d2b0fc4ee17f74e8a6bd3acd6623a05f1bde9e598fa37f3eedfe479da0a00da7827595e0b"))
+        .insert_header(("X-Signature-Timestamp", "1616343571"))
         .set_payload("{\"type\":2,\"token\":\"awQabcabc\",\"member\":{\"user\":{\"id\":\"317209107000066050\",\"username\":\"C0der\",\"avatar\":\"a_d5efa99b3eeaa7dd43acca82f5692432\",\"discriminator\":\"1337\",\"public_flags\":131141},\"roles\":[],\"premium_since\":null,\"permissions\":\"2147483647\",\"pending\":false,\"nick\":null,\"mute\":false,\"joined_at\":\"2017-03-13T19:19:14.040000+00:00\",\"is_pending\":false,\"deaf\":false},\"id\":\"786008729715212338\",\"guild_id\":\"290926798626357999\",\"data\":{\"name\":\"test\",\"id\":\"771825006014889984\"},\"channel_id\":\"645027906669510667\"}")
         .to_request();
 
     let res: types::interaction::InteractionResponse =
-        test::read_response_json(&mut app, req).await;
+        actix_test::call_and_read_body_json(&app, req).await;
 
     let expected_data = InteractionResponse {
         r#type: InteractionResponseType::DefferedChannelMessageWithSource,
@@ -482,19 +1261,19 @@ async fn interactions_deffered_from_direct_value_handle_test() {
     let mut ih = init_handler!();
 
     ih.add_global_command("test", deffered_handle_direct_test);
-    let data = web::Data::new(Mutex::new(ih));
-    let mut app = interaction_app_init!(data);
+    let data = web::Data::new(ih);
+    let app = interaction_app_init!(data);
 
-    let req = test::TestRequest::post()
+    let req = actix_test::TestRequest::post()
         .uri("/api/discord/interactions")
-        .header("Content-Type", "application/json")
-        .header("X-Signature-Ed25519", "a27ed2fd0e91da58667bec63d14406e5274a0427edad9530b7d95e9d2b0fc4ee17f74e8a6bd3acd6623a05f1bde9e598fa37f3eedfe479da0a00da7827595e0b")
-        .header("X-Signature-Timestamp", "1616343571")
+        .insert_header(("Content-Type", "application/json"))
+        .insert_header(("X-Signature-Ed25519", "a27ed2fd0e91da58667bec63d14406e5274a0427edad9530b7d95e9d2b0fc4ee17f74e8a6bd3acd6623a05f1bde9e598fa37f3eedfe479da0a00da7827595e0b"))
+        .insert_header(("X-Signature-Timestamp", "1616343571"))
         .set_payload("{\"type\":2,\"token\":\"awQabcabc\",\"member\":{\"user\":{\"id\":\"317209107000066050\",\"username\":\"C0der\",\"avatar\":\"a_d5efa99b3eeaa7dd43acca82f5692432\",\"discriminator\":\"1337\",\"public_flags\":131141},\"roles\":[],\"premium_since\":null,\"permissions\":\"2147483647\",\"pending\":false,\"nick\":null,\"mute\":false,\"joined_at\":\"2017-03-13T19:19:14.040000+00:00\",\"is_pending\":false,\"deaf\":false},\"id\":\"786008729715212338\",\"guild_id\":\"290926798626357999\",\"data\":{\"name\":\"test\",\"id\":\"771825006014889984\"},\"channel_id\":\"645027906669510667\"}")
         .to_request();
 
     let res: types::interaction::InteractionResponse =
-        test::read_response_json(&mut app, req).await;
+        actix_test::call_and_read_body_json(&app, req).await;
 
     let expected_data = InteractionResponse {
         r#type: InteractionResponseType::DefferedChannelMessageWithSource,
@@ -505,3 +1284,1230 @@ async fn interactions_deffered_from_direct_value_handle_test() {
 
     assert_eq!(res, expected_data);
 }
+
+/*-------------------------------
+Handler tests, calling `InteractionHandler::process` with signed payloads
+*/
+#[cfg(feature = "handler-core")]
+// Private key of the handlers created with `signed_handler`
+const TEST_SIGNING_KEY: [u8; 32] = [7; 32];
+
+#[cfg(feature = "handler-core")]
+// A handler that accepts requests signed with `signed_headers`
+fn signed_handler() -> InteractionHandler {
+    let key = ed25519_dalek::SigningKey::from_bytes(&TEST_SIGNING_KEY);
+    let key = hex::encode(key.verifying_key().to_bytes());
+
+    #[cfg(not(feature = "extended-handler"))]
+    return InteractionHandler::new(0, key, None);
+    #[cfg(feature = "extended-handler")]
+    return InteractionHandler::new(0, key, Some(&String::new()));
+}
+
+#[cfg(feature = "handler-core")]
+// Headers with a valid signature of `body`
+fn signed_headers(body: &str) -> HashMap<String, String> {
+    use ed25519_dalek::Signer;
+
+    let timestamp = "1616343571";
+    let key = ed25519_dalek::SigningKey::from_bytes(&TEST_SIGNING_KEY);
+    let signature = key.sign(format!("{}{}", timestamp, body).as_bytes());
+
+    let mut headers = HashMap::new();
+    headers.insert("Content-Type".to_string(), "application/json".to_string());
+    headers.insert(
+        "X-Signature-Ed25519".to_string(),
+        hex::encode(signature.to_bytes()),
+    );
+    headers.insert("X-Signature-Timestamp".to_string(), timestamp.to_string());
+    headers
+}
+
+#[cfg(feature = "handler")]
+// A slash command used in a guild
+fn command_payload(name: &str) -> String {
+    serde_json::json!({
+        "type": 2,
+        "token": "awQabcabc",
+        "id": "786008729715212338",
+        "guild_id": "290926798626357999",
+        "channel_id": "645027906669510667",
+        "member": {
+            "user": {"id": "317209107000066050", "username": "C0der", "discriminator": "1337"},
+            "roles": [],
+            "permissions": "2147483647",
+            "joined_at": "2017-03-13T19:19:14.040000+00:00",
+            "deaf": false,
+            "mute": false
+        },
+        "data": {"id": "771825006014889984", "name": name}
+    })
+    .to_string()
+}
+
+#[cfg(feature = "handler")]
+// `command_payload`, invoked in a DM by the same user
+fn dm_command_payload(name: &str) -> String {
+    let mut payload: serde_json::Value = serde_json::from_str(&command_payload(name)).unwrap();
+    let member = payload.as_object_mut().unwrap().remove("member").unwrap();
+    payload.as_object_mut().unwrap().remove("guild_id");
+    payload["user"] = member["user"].clone();
+    payload.to_string()
+}
+
+#[cfg(feature = "handler-core")]
+// A button clicked on the response to the interaction of `command_payload`
+fn component_payload(custom_id: &str) -> String {
+    serde_json::json!({
+        "type": 3,
+        "token": "awQabcabc",
+        "id": "786008729715212339",
+        "guild_id": "290926798626357999",
+        "channel_id": "645027906669510667",
+        "member": {
+            "user": {"id": "317209107000066050", "username": "C0der", "discriminator": "1337"},
+            "roles": [],
+            "permissions": "2147483647",
+            "joined_at": "2017-03-13T19:19:14.040000+00:00",
+            "deaf": false,
+            "mute": false
+        },
+        "message": {
+            "id": "846462639134605312",
+            "channel_id": "645027906669510667",
+            "interaction_metadata": {
+                "id": "786008729715212338",
+                "type": 2,
+                "user": {"id": "317209107000066050", "username": "C0der", "discriminator": "1337"}
+            }
+        },
+        "data": {"custom_id": custom_id, "component_type": 2}
+    })
+    .to_string()
+}
+
+#[cfg(feature = "handler")]
+// Sends `body` to the handler, returning the status and the decoded response body
+async fn process_signed(ih: &InteractionHandler, body: &str) -> (u16, serde_json::Value) {
+    let (status, res) = ih.process(&signed_headers(body), body).await;
+    (status, serde_json::from_str(&res).unwrap_or_default())
+}
+
+#[cfg(feature = "handler-core")]
+// Runs `future` on a new runtime, for tests that don't need actix-web
+fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(future)
+}
+
+#[cfg(feature = "handler-core")]
+#[test]
+// `process` answers a signed ping with a pong, whatever the case of the header names
+fn process_ping_test() {
+    let ih = signed_handler();
+    let body = "{\"type\":1}";
+
+    let (status, res) = block_on(ih.process(&signed_headers(body), body));
+    assert_eq!(status, 200);
+    assert_eq!(res, "{\"type\":1}");
+
+    let headers: HashMap<_, _> = signed_headers(body)
+        .into_iter()
+        .map(|(k, v)| (k.to_lowercase(), v))
+        .collect();
+    let (status, _) = block_on(ih.process(&headers, body));
+    assert_eq!(status, 200);
+}
+
+#[cfg(feature = "handler-core")]
+#[test]
+// `process` rejects requests with a bad or missing signature
+fn process_bad_signature_test() {
+    let ih = signed_handler();
+    let body = "{\"type\":1}";
+
+    let headers = signed_headers("{\"type\":2}");
+    let (status, res) = block_on(ih.process(&headers, body));
+    assert_eq!(status, 401);
+    assert!(res.contains("Invalid request signature"));
+
+    let mut headers = signed_headers(body);
+    headers.remove("X-Signature-Ed25519");
+    let (status, res) = block_on(ih.process(&headers, body));
+    assert_eq!(status, 400);
+    assert!(res.contains("Bad signature data"));
+}
+
+#[cfg(feature = "handler")]
+#[slash_command]
+async fn failing_handle_test(_ctx: Context) -> Result<InteractionResponse, std::fmt::Error> {
+    Err(std::fmt::Error)
+}
+
+#[cfg(feature = "handler")]
+#[slash_command]
+#[defer]
+async fn deferred_failing_handle_test(
+    _ctx: Context,
+) -> Result<InteractionResponse, std::fmt::Error> {
+    Err(std::fmt::Error)
+}
+
+#[cfg(feature = "handler")]
+static DEFERRED_ERRORS: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+#[cfg(feature = "handler")]
+fn count_error_reply(ctx: &Context, e: &handler::HandlerError) -> InteractionResponse {
+    DEFERRED_ERRORS.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+    ctx.respond()
+        .content(format!("Failed: {}", e))
+        .build()
+        .unwrap()
+}
+
+#[cfg(feature = "handler")]
+#[actix_rt::test]
+// Errors returned by handlers are turned into a reply by the error hook
+async fn handler_error_reply_test() {
+    let mut ih = signed_handler();
+    ih.add_global_command("fail", failing_handle_test);
+
+    // The default hook replies ephemerally without exposing the error
+    let (status, res) = process_signed(&ih, &command_payload("fail")).await;
+    assert_eq!(status, 200);
+    assert_eq!(res["type"], 4);
+    assert_eq!(res["data"]["flags"], 64);
+    assert_eq!(
+        res["data"]["content"],
+        "Something went wrong while handling this interaction."
+    );
+
+    ih.on_error(|ctx, e| {
+        ctx.respond()
+            .content(format!("Failed: {}", e))
+            .build()
+            .unwrap()
+    });
+    let (_, res) = process_signed(&ih, &command_payload("fail")).await;
+    assert_eq!(
+        res["data"]["content"],
+        "Failed: an error occurred when formatting an argument"
+    );
+}
+
+#[cfg(feature = "handler")]
+#[actix_rt::test]
+// Errors of deferred handlers go through the error hook after the deferred response was sent
+async fn handler_deferred_error_reply_test() {
+    let mut ih = signed_handler();
+    ih.add_global_command("fail", deferred_failing_handle_test);
+    ih.on_error(count_error_reply);
+
+    let (status, res) = process_signed(&ih, &command_payload("fail")).await;
+    assert_eq!(status, 202);
+    assert_eq!(res["type"], 5);
+
+    for _ in 0..100 {
+        if DEFERRED_ERRORS.load(std::sync::atomic::Ordering::SeqCst) > 0 {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+    assert_eq!(DEFERRED_ERRORS.load(std::sync::atomic::Ordering::SeqCst), 1);
+}
+
+#[cfg(feature = "handler")]
+#[slash_command]
+async fn quick_handle_test(ctx: Context) -> InteractionResponse {
+    ctx.respond().content("Quick").build().unwrap()
+}
+
+#[cfg(feature = "handler")]
+#[slash_command]
+async fn slow_handle_test(ctx: Context) -> InteractionResponse {
+    tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+    ctx.respond().content("Slow").build().unwrap()
+}
+
+#[cfg(feature = "handler")]
+#[component_handler]
+async fn slow_component_handle_test(ctx: Context) -> InteractionResponse {
+    tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+    ctx.respond().content("Slow").build().unwrap()
+}
+
+#[cfg(feature = "handler")]
+#[actix_rt::test]
+// Handlers that finish within the auto-defer budget respond inline, slower ones are deferred
+async fn handler_auto_defer_test() {
+    let mut ih = signed_handler();
+    ih.auto_defer(std::time::Duration::from_millis(100));
+    ih.add_global_command("quick", quick_handle_test);
+    ih.add_global_command("slow", slow_handle_test);
+    ih.add_component_handle("slow", slow_component_handle_test);
+
+    let (status, res) = process_signed(&ih, &command_payload("quick")).await;
+    assert_eq!(status, 200);
+    assert_eq!(res["type"], 4);
+    assert_eq!(res["data"]["content"], "Quick");
+
+    let (status, res) = process_signed(&ih, &command_payload("slow")).await;
+    assert_eq!(status, 202);
+    assert_eq!(res["type"], 5);
+
+    let (status, res) = process_signed(&ih, &component_payload("slow")).await;
+    assert_eq!(status, 202);
+    assert_eq!(res["type"], 6);
+}
+
+#[cfg(feature = "handler")]
+#[test]
+// A late response only replaces the deferred response if it is of the same kind
+fn handler_late_delivery_test() {
+    use crate::handler::{late_delivery, LateDelivery};
+    use InteractionResponseType::{
+        ChannelMessageWithSource, DefferedChannelMessageWithSource, DefferedUpdateMessage,
+        PremiumRequired, UpdateMessage,
+    };
+
+    assert_eq!(
+        late_delivery(&DefferedChannelMessageWithSource, &ChannelMessageWithSource),
+        Some(LateDelivery::EditOriginal)
+    );
+    assert_eq!(
+        late_delivery(&DefferedUpdateMessage, &UpdateMessage),
+        Some(LateDelivery::EditOriginal)
+    );
+    // A new message must not overwrite the message of the component
+    assert_eq!(
+        late_delivery(&DefferedUpdateMessage, &ChannelMessageWithSource),
+        Some(LateDelivery::Followup)
+    );
+    assert_eq!(
+        late_delivery(&DefferedChannelMessageWithSource, &UpdateMessage),
+        Some(LateDelivery::Followup)
+    );
+    assert_eq!(
+        late_delivery(&DefferedUpdateMessage, &PremiumRequired),
+        None
+    );
+}
+
+#[cfg(feature = "handler-core")]
+#[test]
+// A late response without data is dropped instead of panicking, without contacting Discord
+fn handler_late_dataless_test() {
+    use crate::handler::deliver_late;
+    use crate::types::interaction::{InteractionResponse, InteractionResponseType};
+    use InteractionResponseType::{
+        ChannelMessageWithSource, DefferedChannelMessageWithSource, DefferedUpdateMessage,
+        UpdateMessage,
+    };
+
+    let ctx = cooldown_ctx(317209107000066050, None, 290926798626357999);
+    block_on(async {
+        // Would edit the deferred response
+        deliver_late(
+            &ctx,
+            &DefferedChannelMessageWithSource,
+            InteractionResponse::new(ChannelMessageWithSource, None),
+        )
+        .await;
+        // Would be sent as a followup
+        deliver_late(
+            &ctx,
+            &DefferedUpdateMessage,
+            InteractionResponse::new(ChannelMessageWithSource, None),
+        )
+        .await;
+        deliver_late(
+            &ctx,
+            &DefferedChannelMessageWithSource,
+            InteractionResponse::new(UpdateMessage, None),
+        )
+        .await;
+    });
+}
+
+#[cfg(feature = "handler")]
+// Records when its hooks run, and responds in `before` if `respond` is set
+struct RecordingMiddleware {
+    name: &'static str,
+    respond: bool,
+    log: std::sync::Arc<std::sync::Mutex<Vec<String>>>,
+}
+
+#[cfg(feature = "handler")]
+#[crate::middleware::async_trait]
+impl crate::middleware::Middleware for RecordingMiddleware {
+    async fn before(&self, ctx: &Context) -> Option<InteractionResponse> {
+        self.log
+            .lock()
+            .unwrap()
+            .push(format!("{} before", self.name));
+        if self.respond {
+            Some(ctx.respond().content(self.name).build().unwrap())
+        } else {
+            None
+        }
+    }
+
+    async fn after(&self, _ctx: &Context, _response: &mut InteractionResponse) {
+        self.log
+            .lock()
+            .unwrap()
+            .push(format!("{} after", self.name));
+    }
+}
+
+#[cfg(feature = "handler")]
+#[actix_rt::test]
+// The first added middleware is the outermost, and a middleware that responds skips the handler
+async fn handler_middleware_test() {
+    let log = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let middleware = |name, respond| RecordingMiddleware {
+        name,
+        respond,
+        log: log.clone(),
+    };
+
+    let mut ih = signed_handler();
+    ih.add_global_command("quick", quick_handle_test);
+    ih.add_middleware(middleware("outer", false));
+    ih.add_middleware(middleware("inner", false));
+
+    let (_, res) = process_signed(&ih, &command_payload("quick")).await;
+    assert_eq!(res["data"]["content"], "Quick");
+    assert_eq!(
+        *log.lock().unwrap(),
+        ["outer before", "inner before", "inner after", "outer after"]
+    );
+
+    log.lock().unwrap().clear();
+    let mut ih = signed_handler();
+    ih.add_global_command("quick", quick_handle_test);
+    ih.add_middleware(middleware("outer", false));
+    ih.add_middleware(middleware("blocking", true));
+    ih.add_middleware(middleware("inner", false));
+
+    let (_, res) = process_signed(&ih, &command_payload("quick")).await;
+    assert_eq!(res["data"]["content"], "blocking");
+    assert_eq!(
+        *log.lock().unwrap(),
+        [
+            "outer before",
+            "blocking before",
+            "blocking after",
+            "outer after"
+        ]
+    );
+}
+
+#[cfg(feature = "handler-core")]
+// A command used by `user`, in `guild` if set, and in `channel`
+fn cooldown_ctx(user: u64, guild: Option<u64>, channel: u64) -> Context {
+    let user_json =
+        serde_json::json!({"id": user.to_string(), "username": "C0der", "discriminator": "1337"});
+    let mut payload = serde_json::json!({
+        "type": 2,
+        "token": "awQabcabc",
+        "id": "786008729715212338",
+        "channel_id": channel.to_string(),
+        "data": {"id": "771825006014889984", "name": "test"}
+    });
+    match guild {
+        Some(g) => {
+            payload["guild_id"] = g.to_string().into();
+            payload["member"] = serde_json::json!({
+                "user": user_json,
+                "roles": [],
+                "joined_at": "2017-03-13T19:19:14.040000+00:00",
+                "deaf": false,
+                "mute": false
+            });
+        }
+        None => payload["user"] = user_json,
+    }
+    // Building a client is slow, contexts share one
+    static CLIENT: std::sync::OnceLock<reqwest::Client> = std::sync::OnceLock::new();
+    Context::new(
+        CLIENT.get_or_init(reqwest::Client::new).clone(),
+        serde_json::from_value(payload).unwrap(),
+    )
+}
+
+#[cfg(feature = "handler-core")]
+#[test]
+// Cooldowns are tracked per bucket and report the time left
+fn cooldown_check_test() {
+    use crate::cooldown::{Cooldown, CooldownBucket};
+    use std::time::Duration;
+
+    let minute = Duration::from_secs(60);
+
+    let cooldown = Cooldown::new(minute, CooldownBucket::User);
+    assert!(cooldown.check(&cooldown_ctx(1, Some(10), 100)).is_ok());
+    let remaining = cooldown.check(&cooldown_ctx(1, Some(10), 100)).unwrap_err();
+    assert!(remaining <= minute && remaining > minute - Duration::from_secs(5));
+    // Another user, and the same user elsewhere
+    assert!(cooldown.check(&cooldown_ctx(2, Some(10), 100)).is_ok());
+    assert!(cooldown.check(&cooldown_ctx(1, Some(11), 101)).is_err());
+
+    // Users share the cooldown of a guild, but each has their own in DMs
+    let cooldown = Cooldown::new(minute, CooldownBucket::Guild);
+    assert!(cooldown.check(&cooldown_ctx(1, Some(10), 100)).is_ok());
+    assert!(cooldown.check(&cooldown_ctx(2, Some(10), 101)).is_err());
+    assert!(cooldown.check(&cooldown_ctx(2, Some(11), 100)).is_ok());
+    assert!(cooldown.check(&cooldown_ctx(3, None, 102)).is_ok());
+    assert!(cooldown.check(&cooldown_ctx(4, None, 102)).is_ok());
+    assert!(cooldown.check(&cooldown_ctx(3, None, 103)).is_err());
+
+    let cooldown = Cooldown::new(minute, CooldownBucket::Channel);
+    assert!(cooldown.check(&cooldown_ctx(1, Some(10), 100)).is_ok());
+    assert!(cooldown.check(&cooldown_ctx(2, Some(11), 100)).is_err());
+    assert!(cooldown.check(&cooldown_ctx(1, Some(10), 101)).is_ok());
+
+    let cooldown = Cooldown::new(minute, CooldownBucket::Global);
+    assert!(cooldown.check(&cooldown_ctx(1, Some(10), 100)).is_ok());
+    assert!(cooldown.check(&cooldown_ctx(2, None, 101)).is_err());
+
+    // Clones share their state
+    let clone = cooldown.clone();
+    assert!(clone.check(&cooldown_ctx(3, None, 102)).is_err());
+
+    // The cooldown ends after its duration
+    let ctx = cooldown_ctx(1, None, 100);
+    let cooldown = Cooldown::new(Duration::from_millis(100), CooldownBucket::User);
+    assert!(cooldown.check(&ctx).is_ok());
+    assert!(cooldown.check(&ctx).is_err());
+    std::thread::sleep(Duration::from_millis(150));
+    assert!(cooldown.check(&ctx).is_ok());
+}
+
+#[cfg(feature = "handler-core")]
+#[test]
+// Exempt interactions are not limited and don't start a cooldown
+fn cooldown_exempt_test() {
+    use crate::cooldown::{Cooldown, CooldownBucket};
+    use std::time::Duration;
+
+    let cooldown = Cooldown::new(Duration::from_secs(60), CooldownBucket::Global)
+        .exempt(|ctx| ctx.author_id == Some(1.into()));
+
+    assert!(cooldown.check(&cooldown_ctx(1, None, 100)).is_ok());
+    assert!(cooldown.check(&cooldown_ctx(1, None, 100)).is_ok());
+    assert!(cooldown.check(&cooldown_ctx(2, None, 100)).is_ok());
+    assert!(cooldown.check(&cooldown_ctx(2, None, 100)).is_err());
+    assert!(cooldown.check(&cooldown_ctx(1, None, 100)).is_ok());
+}
+
+#[cfg(feature = "handler-core")]
+#[test]
+// Expired buckets are dropped once the cleanup threshold is reached, active ones are kept
+fn cooldown_cleanup_test() {
+    use crate::cooldown::{Cooldown, CooldownBucket, CLEANUP_THRESHOLD};
+    use std::time::Duration;
+
+    let contexts: Vec<_> = (0..=CLEANUP_THRESHOLD as u64)
+        .map(|user| cooldown_ctx(user, None, 100))
+        .collect();
+
+    let cooldown = Cooldown::new(Duration::from_millis(100), CooldownBucket::User);
+    for ctx in &contexts[1..] {
+        assert!(cooldown.check(ctx).is_ok());
+    }
+    assert_eq!(cooldown.tracked(), CLEANUP_THRESHOLD);
+
+    std::thread::sleep(Duration::from_millis(150));
+    assert!(cooldown.check(&contexts[0]).is_ok());
+    assert_eq!(cooldown.tracked(), 1);
+
+    let cooldown = Cooldown::new(Duration::from_secs(60), CooldownBucket::User);
+    for ctx in &contexts {
+        assert!(cooldown.check(ctx).is_ok());
+    }
+    assert_eq!(cooldown.tracked(), CLEANUP_THRESHOLD + 1);
+    assert!(cooldown.check(&contexts[0]).is_err());
+}
+
+#[cfg(feature = "handler")]
+// Counts the uses of `count_handle_test` and `deferred_count_handle_test`
+struct UseCount(std::sync::atomic::AtomicUsize);
+
+#[cfg(feature = "handler")]
+#[slash_command]
+async fn count_handle_test(handler: &InteractionHandler, ctx: Context) -> InteractionResponse {
+    let count = handler.state::<UseCount>().unwrap();
+    count.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+    ctx.respond().content("Counted").build().unwrap()
+}
+
+#[cfg(feature = "handler")]
+#[slash_command]
+#[defer]
+async fn deferred_count_handle_test(
+    handler: &InteractionHandler,
+    ctx: Context,
+) -> InteractionResponse {
+    let count = handler.state::<UseCount>().unwrap();
+    count.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+    ctx.respond().content("Counted").build().unwrap()
+}
+
+#[cfg(feature = "handler")]
+#[actix_rt::test]
+// Deferred handlers run on a clone of the handler, which shares its state
+async fn handler_shared_state_test() {
+    use std::sync::atomic::Ordering;
+
+    let mut ih = signed_handler();
+    ih.add_state(UseCount(std::sync::atomic::AtomicUsize::new(0)));
+    ih.add_global_command("count", count_handle_test);
+    ih.add_global_command("deferred", deferred_count_handle_test);
+
+    let (_, res) = process_signed(&ih, &command_payload("count")).await;
+    assert_eq!(res["data"]["content"], "Counted");
+    let (status, _) = process_signed(&ih, &command_payload("deferred")).await;
+    assert_eq!(status, 202);
+
+    let count = ih.state::<UseCount>().unwrap();
+    for _ in 0..100 {
+        if count.0.load(Ordering::SeqCst) == 2 {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+    assert_eq!(count.0.load(Ordering::SeqCst), 2);
+}
+
+#[cfg(feature = "handler-core")]
+#[test]
+// Collectors only accept components that match every filter they have
+fn collector_filter_test() {
+    use crate::collector::ComponentFilter;
+
+    let ctx = Context::new(
+        reqwest::Client::new(),
+        serde_json::from_str(&component_payload("yes")).unwrap(),
+    );
+
+    assert!(ComponentFilter::default().matches(&ctx));
+
+    let filter = ComponentFilter {
+        message: Some(846462639134605312.into()),
+        original: Some(786008729715212338.into()),
+        user: Some(317209107000066050.into()),
+        custom_ids: vec!["no".to_string(), "yes".to_string()],
+    };
+    assert!(filter.matches(&ctx));
+
+    let wrong = [
+        ComponentFilter {
+            message: Some(1.into()),
+            ..filter.clone()
+        },
+        ComponentFilter {
+            original: Some(1.into()),
+            ..filter.clone()
+        },
+        ComponentFilter {
+            user: Some(1.into()),
+            ..filter.clone()
+        },
+        ComponentFilter {
+            custom_ids: vec!["no".to_string()],
+            ..filter.clone()
+        },
+    ];
+    for f in &wrong {
+        assert!(!f.matches(&ctx), "{:?} matched", f);
+    }
+}
+
+#[cfg(feature = "handler")]
+#[actix_rt::test]
+// Collected components go through the middleware and cooldowns before they are delivered
+async fn handler_collector_test() {
+    use crate::cooldown::{Cooldown, CooldownBucket};
+    use std::time::Duration;
+
+    let log = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let middleware = |name, respond| RecordingMiddleware {
+        name,
+        respond,
+        log: log.clone(),
+    };
+
+    let mut ih = signed_handler();
+    ih.add_middleware(middleware("outer", false));
+    ih.set_cooldown(
+        "yes",
+        Cooldown::new(Duration::from_secs(60), CooldownBucket::User),
+    );
+
+    let collector = ih.collect_component().custom_id("yes");
+    let waiting = actix::spawn(collector.timeout(Duration::from_secs(5)).next());
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let (status, _) = process_signed(&ih, &component_payload("yes")).await;
+    assert_eq!(status, 202);
+    let collected = waiting.await.unwrap().unwrap();
+    assert_eq!(
+        collected.interaction.data.unwrap().custom_id.as_deref(),
+        Some("yes")
+    );
+    assert_eq!(*log.lock().unwrap(), ["outer before", "outer after"]);
+
+    // The second click is on cooldown, so the collector doesn't get it
+    let collector = ih.collect_component().custom_id("yes");
+    let waiting = actix::spawn(collector.timeout(Duration::from_millis(300)).next());
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let (_, res) = process_signed(&ih, &component_payload("yes")).await;
+    assert_eq!(res["data"]["flags"], 64);
+    assert!(waiting.await.unwrap().is_none());
+
+    // A middleware that responds keeps the component from the collector
+    let mut ih = signed_handler();
+    ih.add_middleware(middleware("blocking", true));
+
+    let collector = ih.collect_component().custom_id("yes");
+    let waiting = actix::spawn(collector.timeout(Duration::from_millis(300)).next());
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let (_, res) = process_signed(&ih, &component_payload("yes")).await;
+    assert_eq!(res["data"]["content"], "blocking");
+    assert!(waiting.await.unwrap().is_none());
+}
+
+#[cfg(feature = "handler")]
+#[slash_command]
+async fn confirm_handle_test(handler: &InteractionHandler, ctx: Context) -> InteractionResponse {
+    let click = handler
+        .collect_component()
+        .custom_id("yes")
+        .timeout(std::time::Duration::from_secs(5))
+        .next()
+        .await;
+
+    let content = if click.is_some() {
+        "Confirmed"
+    } else {
+        "Timed out"
+    };
+    ctx.respond().content(content).build().unwrap()
+}
+
+#[cfg(feature = "handler")]
+#[actix_rt::test]
+// Interactions are handled concurrently, so a handler can wait for a component that arrives while it runs
+async fn handler_concurrent_test() {
+    let mut ih = signed_handler();
+    ih.add_global_command("confirm", confirm_handle_test);
+    let app =
+        actix_test::init_service(App::new().service(ih.into_shared().resource("/interactions")))
+            .await;
+
+    let request = |body: String| {
+        let mut req = actix_test::TestRequest::post().uri("/interactions");
+        for header in signed_headers(&body) {
+            req = req.insert_header(header);
+        }
+        req.set_payload(body).to_request()
+    };
+
+    let command = async {
+        let res: serde_json::Value =
+            actix_test::call_and_read_body_json(&app, request(command_payload("confirm"))).await;
+        res
+    };
+    let click = async {
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        actix_test::call_service(&app, request(component_payload("yes")))
+            .await
+            .status()
+    };
+
+    let (res, status) = tokio::join!(command, click);
+    assert_eq!(res["data"]["content"], "Confirmed");
+    assert_eq!(status, 202);
+}
+
+#[cfg(feature = "extended-handler")]
+#[slash_command]
+#[defer]
+// Registers `quick_handle_test` for the guild command with ID 771825006014889985
+async fn register_handle_test(handler: &InteractionHandler, ctx: Context) -> InteractionResponse {
+    use crate::handler::ManipulationScope;
+
+    let cmd = serde_json::from_str(
+        "{\"id\":\"771825006014889985\",\"name\":\"registered\",\"description\":\"Test\"}",
+    )
+    .unwrap();
+    handler
+        .register_guild_handle(
+            290926798626357999,
+            &cmd,
+            quick_handle_test,
+            &ManipulationScope::Local,
+        )
+        .await
+        .unwrap();
+    ctx.respond().content("Registered").build().unwrap()
+}
+
+#[cfg(feature = "extended-handler")]
+#[actix_rt::test]
+// Handlers share the handler, so a guild handle registered by a deferred handler is used for later interactions
+async fn handler_shared_registration_test() {
+    let mut ih = signed_handler();
+    ih.add_global_command("register", register_handle_test);
+
+    let mut registered: serde_json::Value =
+        serde_json::from_str(&command_payload("registered")).unwrap();
+    registered["data"]["id"] = "771825006014889985".into();
+    let registered = registered.to_string();
+
+    let (status, _) = process_signed(&ih, &registered).await;
+    assert_eq!(status, 501);
+
+    let (status, _) = process_signed(&ih, &command_payload("register")).await;
+    assert_eq!(status, 202);
+
+    let mut res = process_signed(&ih, &registered).await;
+    for _ in 0..100 {
+        if res.0 == 200 {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        res = process_signed(&ih, &registered).await;
+    }
+    assert_eq!(res.1["data"]["content"], "Quick");
+}
+
+#[cfg(feature = "handler-core")]
+// `cooldown_ctx` with the member holding `roles` and `permissions`
+fn guard_ctx(roles: &[u64], permissions: Option<&str>) -> Context {
+    use crate::types::id::RoleId;
+
+    let mut ctx = cooldown_ctx(
+        317209107000066050,
+        Some(290926798626357999),
+        645027906669510667,
+    );
+    let member = ctx.interaction.member.as_mut().unwrap();
+    member.roles = roles.iter().map(|r| RoleId::from(*r)).collect();
+    member.permissions = permissions.map(String::from);
+    ctx
+}
+
+#[cfg(feature = "handler-core")]
+#[test]
+// Guild and DM guards check where the interaction was invoked
+fn guard_context_test() {
+    use crate::guard::*;
+    use crate::types::guild::Permissions;
+
+    let guild = guard_ctx(&[], None);
+    let dm = cooldown_ctx(317209107000066050, None, 645027906669510667);
+
+    assert_eq!(guild_only(&guild), Ok(()));
+    assert_eq!(guild_only(&dm), Err(GuardError::GuildOnly));
+    assert_eq!(dm_only(&guild), Err(GuardError::DmOnly));
+    assert_eq!(dm_only(&dm), Ok(()));
+    // Roles and permissions only exist in guilds
+    assert_eq!(require_roles(&dm, &[]), Err(GuardError::GuildOnly));
+    assert_eq!(
+        require_permissions(&dm, Permissions::empty()),
+        Err(GuardError::GuildOnly)
+    );
+}
+
+#[cfg(feature = "handler-core")]
+#[test]
+// `owner_only` only allows users added with `add_owner`
+fn guard_owner_test() {
+    use crate::guard::*;
+
+    let mut ih = signed_handler();
+    let ctx = guard_ctx(&[], None);
+    assert_eq!(owner_only(&ih, &ctx), Err(GuardError::OwnerOnly));
+
+    ih.add_owner(317209107000066050);
+    assert_eq!(owner_only(&ih, &ctx), Ok(()));
+}
+
+#[cfg(feature = "handler-core")]
+#[test]
+// The member needs every required role
+fn guard_roles_test() {
+    use crate::guard::*;
+    use crate::types::id::RoleId;
+
+    let ctx = guard_ctx(&[1, 2], None);
+    assert_eq!(require_roles(&ctx, &[]), Ok(()));
+    assert_eq!(require_roles(&ctx, &[RoleId::from(1)]), Ok(()));
+    assert_eq!(
+        require_roles(&ctx, &[RoleId::from(1), RoleId::from(2)]),
+        Ok(())
+    );
+    assert_eq!(
+        require_roles(&ctx, &[RoleId::from(1), RoleId::from(3)]),
+        Err(GuardError::MissingRoles)
+    );
+    assert_eq!(
+        require_roles(&guard_ctx(&[], None), &[RoleId::from(1)]),
+        Err(GuardError::MissingRoles)
+    );
+}
+
+#[cfg(feature = "handler-core")]
+#[test]
+// A rejection lists exactly the missing permissions, and administrators are always allowed
+fn guard_permissions_test() {
+    use crate::guard::*;
+    use crate::types::guild::Permissions as P;
+
+    // MANAGE_GUILD and BAN_MEMBERS
+    let ctx = guard_ctx(&[], Some("36"));
+    assert_eq!(require_permissions(&ctx, P::MANAGE_GUILD), Ok(()));
+    assert_eq!(
+        require_permissions(&ctx, P::MANAGE_GUILD | P::BAN_MEMBERS),
+        Ok(())
+    );
+    assert_eq!(
+        require_permissions(&ctx, P::MANAGE_GUILD | P::BAN_MEMBERS | P::KICK_MEMBERS),
+        Err(GuardError::MissingPermissions(P::KICK_MEMBERS))
+    );
+
+    // Without permissions in the payload, everything is missing
+    let ctx = guard_ctx(&[], None);
+    assert_eq!(
+        require_permissions(&ctx, P::MANAGE_GUILD | P::KICK_MEMBERS),
+        Err(GuardError::MissingPermissions(
+            P::MANAGE_GUILD | P::KICK_MEMBERS
+        ))
+    );
+
+    let ctx = guard_ctx(&[], Some("8"));
+    assert_eq!(
+        require_permissions(&ctx, P::MANAGE_GUILD | P::KICK_MEMBERS),
+        Ok(())
+    );
+}
+
+#[cfg(feature = "handler-core")]
+#[test]
+// `__guard` turns a rejection into an ephemeral reply explaining it
+fn guard_reply_test() {
+    use crate::guard::*;
+
+    let ctx = guard_ctx(&[], None);
+    assert!(__guard(Ok(()), &ctx).is_none());
+
+    let reply = serde_json::to_value(__guard(Err(GuardError::GuildOnly), &ctx).unwrap()).unwrap();
+    assert_eq!(reply["type"], 4);
+    assert_eq!(reply["data"]["flags"], 64);
+    assert_eq!(
+        reply["data"]["content"],
+        "This can only be used in a server."
+    );
+}
+
+#[cfg(feature = "handler")]
+#[slash_command]
+#[guild_only]
+#[require_permissions(MANAGE_GUILD)]
+async fn guarded_handle_test(ctx: Context) -> InteractionResponse {
+    ctx.respond().content("Guarded").build().unwrap()
+}
+
+#[cfg(feature = "handler")]
+#[actix_rt::test]
+// Guard attributes reject interactions with an ephemeral reply before the handler runs
+async fn handler_guard_test() {
+    let mut ih = signed_handler();
+    ih.add_global_command("guarded", guarded_handle_test);
+
+    let (status, res) = process_signed(&ih, &dm_command_payload("guarded")).await;
+    assert_eq!(status, 200);
+    assert_eq!(res["data"]["flags"], 64);
+    assert_eq!(res["data"]["content"], "This can only be used in a server.");
+
+    let mut unprivileged: serde_json::Value =
+        serde_json::from_str(&command_payload("guarded")).unwrap();
+    unprivileged["member"]["permissions"] = "4".into();
+    let (status, res) = process_signed(&ih, &unprivileged.to_string()).await;
+    assert_eq!(status, 200);
+    assert_eq!(res["data"]["flags"], 64);
+    assert_eq!(
+        res["data"]["content"],
+        "You need the following permissions to use this: MANAGE_GUILD"
+    );
+
+    let (_, res) = process_signed(&ih, &command_payload("guarded")).await;
+    assert_eq!(res["data"]["content"], "Guarded");
+    assert!(res["data"]["flags"].is_null());
+}
+
+#[cfg(feature = "handler")]
+// Takes its time before letting an interaction through
+struct DelayMiddleware(std::time::Duration);
+
+#[cfg(feature = "handler")]
+#[crate::middleware::async_trait]
+impl crate::middleware::Middleware for DelayMiddleware {
+    async fn before(&self, _ctx: &Context) -> Option<InteractionResponse> {
+        tokio::time::sleep(self.0).await;
+        None
+    }
+}
+
+#[cfg(feature = "handler")]
+#[component_handler]
+async fn quick_component_handle_test(ctx: Context) -> InteractionResponse {
+    ctx.respond().content("Quick").build().unwrap()
+}
+
+#[cfg(feature = "handler")]
+#[actix_rt::test]
+// A component whose collector stopped waiting while the middleware ran goes to its static route, or is unknown
+async fn handler_collector_timeout_test() {
+    use std::time::Duration;
+
+    let mut ih = signed_handler();
+    ih.add_middleware(DelayMiddleware(Duration::from_millis(200)));
+
+    let collector = ih.collect_component().custom_id("yes");
+    let waiting = actix::spawn(collector.timeout(Duration::from_millis(100)).next());
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let (status, res) = process_signed(&ih, &component_payload("yes")).await;
+    assert_eq!(status, 501);
+    assert_eq!(res["message"], "No associated handler found");
+    assert!(waiting.await.unwrap().is_none());
+
+    ih.add_component_handle("yes", quick_component_handle_test);
+    let collector = ih.collect_component().custom_id("yes");
+    let waiting = actix::spawn(collector.timeout(Duration::from_millis(100)).next());
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let (status, res) = process_signed(&ih, &component_payload("yes")).await;
+    assert_eq!(status, 200);
+    assert_eq!(res["data"]["content"], "Quick");
+    assert!(waiting.await.unwrap().is_none());
+}
+
+#[cfg(feature = "handler")]
+// The options of `extract_handle_test`
+#[derive(serde::Deserialize)]
+struct ExtractArgs {
+    user: crate::types::id::UserId,
+    reason: Option<String>,
+}
+
+#[cfg(feature = "handler")]
+// State for `extract_handle_test`
+struct Prefix(&'static str);
+
+#[cfg(feature = "handler")]
+#[slash_command]
+// Answers with everything it extracted, as JSON
+async fn extract_handle_test(
+    ctx: Context,
+    user: crate::types::user::User,
+    member: crate::types::user::Member,
+    guild: crate::types::id::GuildId,
+    channel: crate::types::id::ChannelId,
+    locale: crate::extract::Locale,
+    missing: Option<crate::extract::State<String>>,
+    crate::extract::State(prefix): crate::extract::State<Prefix>,
+    crate::extract::Options(args): crate::extract::Options<ExtractArgs>,
+) -> InteractionResponse {
+    let content = serde_json::json!({
+        "user": user.id,
+        "member": member.user.id,
+        "guild": guild,
+        "channel": channel,
+        "locale": locale.0,
+        "missing": missing.is_none(),
+        "prefix": prefix.0,
+        "args_user": args.user,
+        "args_reason": args.reason,
+    });
+    ctx.respond().content(content.to_string()).build().unwrap()
+}
+
+#[cfg(feature = "handler")]
+#[actix_rt::test]
+// Every parameter of a handler is extracted from the interaction and the handler
+async fn handler_extract_test() {
+    let mut ih = signed_handler();
+    ih.add_global_command("extract", extract_handle_test);
+    ih.add_state(Prefix("!"));
+
+    let mut payload: serde_json::Value = serde_json::from_str(&command_payload("extract")).unwrap();
+    payload["locale"] = "en-US".into();
+    payload["data"]["options"] = serde_json::json!([
+        {"name": "user", "type": 6, "value": "290926798626357999"},
+        {"name": "reason", "type": 3, "value": "Spam"}
+    ]);
+
+    let (status, res) = process_signed(&ih, &payload.to_string()).await;
+    assert_eq!(status, 200);
+    let extracted: serde_json::Value =
+        serde_json::from_str(res["data"]["content"].as_str().unwrap()).unwrap();
+    assert_eq!(
+        extracted,
+        serde_json::json!({
+            "user": "317209107000066050",
+            "member": "317209107000066050",
+            "guild": "290926798626357999",
+            "channel": "645027906669510667",
+            "locale": "en-US",
+            "missing": true,
+            "prefix": "!",
+            "args_user": "290926798626357999",
+            "args_reason": "Spam",
+        })
+    );
+}
+
+#[cfg(feature = "handler")]
+#[slash_command]
+async fn member_handle_test(
+    ctx: Context,
+    member: crate::types::user::Member,
+) -> InteractionResponse {
+    ctx.respond().content(member.user.username).build().unwrap()
+}
+
+#[cfg(feature = "handler")]
+#[slash_command]
+async fn state_handle_test(
+    ctx: Context,
+    crate::extract::State(prefix): crate::extract::State<Prefix>,
+) -> InteractionResponse {
+    ctx.respond().content(prefix.0).build().unwrap()
+}
+
+#[cfg(feature = "handler")]
+#[actix_rt::test]
+// A failed extraction gets an ephemeral reply describing it, without calling the handler
+async fn handler_extract_error_test() {
+    let mut ih = signed_handler();
+    ih.add_global_command("member", member_handle_test);
+    ih.add_global_command("extract", extract_handle_test);
+    ih.add_state(Prefix("!"));
+
+    let (status, res) = process_signed(&ih, &dm_command_payload("member")).await;
+    assert_eq!(status, 200);
+    assert_eq!(res["data"]["flags"], 64);
+    assert_eq!(res["data"]["content"], "This can only be used in a server.");
+
+    let (_, res) = process_signed(&ih, &command_payload("member")).await;
+    assert_eq!(res["data"]["content"], "C0der");
+
+    // The options are missing the required user
+    let mut payload: serde_json::Value = serde_json::from_str(&command_payload("extract")).unwrap();
+    payload["locale"] = "en-US".into();
+    let (_, res) = process_signed(&ih, &payload.to_string()).await;
+    assert_eq!(res["data"]["flags"], 64);
+    assert!(res["data"]["content"]
+        .as_str()
+        .unwrap()
+        .starts_with("Invalid options: missing field `user`"));
+
+    // `Prefix` was never added
+    let mut ih = signed_handler();
+    ih.add_global_command("state", state_handle_test);
+    let (status, res) = process_signed(&ih, &command_payload("state")).await;
+    assert_eq!(status, 200);
+    assert_eq!(res["data"]["flags"], 64);
+    assert_eq!(
+        res["data"]["content"],
+        format!(
+            "Something went wrong: `{}` is not available.",
+            std::any::type_name::<Prefix>()
+        )
+    );
+}
+
+#[cfg(feature = "tower")]
+// Sends `body` with `headers` through the tower service of `ih`
+fn call_service(
+    ih: InteractionHandler,
+    headers: &HashMap<String, String>,
+    body: String,
+) -> http::Response<String> {
+    use tower_service::Service;
+
+    let mut req = http::Request::post("/api/discord/interactions");
+    for (k, v) in headers {
+        req = req.header(k.as_str(), v.as_str());
+    }
+    block_on(ih.into_service().call(req.body(body).unwrap())).unwrap()
+}
+
+#[cfg(feature = "tower")]
+#[test]
+// The tower service answers like `process`
+fn tower_service_test() {
+    let body = "{\"type\":1}";
+
+    let headers = signed_headers(body);
+    let res = call_service(signed_handler(), &headers, body.to_string());
+    let (status, expected) = block_on(signed_handler().process(&headers, body));
+    assert_eq!(res.status(), status);
+    assert_eq!(res.body(), &expected);
+    assert_eq!(res.body(), "{\"type\":1}");
+    assert_eq!(res.headers()["content-type"], "application/json");
+
+    let headers = signed_headers("{\"type\":2}");
+    let res = call_service(signed_handler(), &headers, body.to_string());
+    let (status, expected) = block_on(signed_handler().process(&headers, body));
+    assert_eq!(res.status(), 401);
+    assert_eq!(res.status(), status);
+    assert_eq!(res.body(), &expected);
+}
+
+#[cfg(feature = "tower")]
+#[test]
+// The tower service rejects bodies over 256 KiB before checking them
+fn tower_service_body_limit_test() {
+    let body = format!("{{\"type\":1,\"padding\":\"{}\"}}", "a".repeat(256 * 1024));
+
+    let res = call_service(signed_handler(), &signed_headers(&body), body);
+    assert_eq!(res.status(), 400);
+    let res: serde_json::Value = serde_json::from_str(res.body()).unwrap();
+    assert_eq!(res["message"], "Failed to read body");
+}
+
+#[cfg(feature = "handler")]
+#[actix_rt::test]
+// A use rejected by a guard doesn't start the cooldown set with `set_cooldown`
+async fn handler_guard_cooldown_test() {
+    use crate::cooldown::{Cooldown, CooldownBucket};
+    use std::time::Duration;
+
+    let mut ih = signed_handler();
+    ih.add_global_command("guarded", guarded_handle_test);
+    ih.set_cooldown(
+        "guarded",
+        Cooldown::new(Duration::from_secs(60), CooldownBucket::User),
+    );
+
+    let (_, res) = process_signed(&ih, &dm_command_payload("guarded")).await;
+    assert_eq!(res["data"]["content"], "This can only be used in a server.");
+
+    // The same user isn't on cooldown yet
+    let (_, res) = process_signed(&ih, &command_payload("guarded")).await;
+    assert_eq!(res["data"]["content"], "Guarded");
+
+    let (_, res) = process_signed(&ih, &command_payload("guarded")).await;
+    assert_eq!(res["data"]["flags"], 64);
+    assert_ne!(res["data"]["content"], "Guarded");
+}
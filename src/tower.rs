@@ -0,0 +1,98 @@
+//! Serve interactions from any framework built on [tower](https://docs.rs/tower), like axum or hyper.
+//!
+//! ```ignore
+//! let handle = InteractionHandler::new(APP_ID, PUB_KEY, Some(&TOKEN.to_string()));
+//!
+//! let app = axum::Router::new().route_service("/api/discord/interactions", handle.into_service());
+//! ```
+use std::convert::Infallible;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use http::header::{HeaderMap, CONTENT_TYPE};
+use http::{Request, Response};
+use http_body::Body;
+use http_body_util::{BodyExt, Limited};
+use log::debug;
+use tower_service::Service;
+
+use crate::handler::{InteractionHandler, RequestHeaders};
+
+// Bodies larger than this are rejected. Interactions are far smaller
+const BODY_LIMIT: usize = 256 * 1024;
+
+impl RequestHeaders for HeaderMap {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.get(name)?.to_str().ok()
+    }
+}
+
+impl InteractionHandler {
+    /// Turn the handler into a tower [`Service`] that handles interactions
+    pub fn into_service(self) -> InteractionService {
+        InteractionService {
+            handler: Arc::new(self),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+/// A tower [`Service`] that handles interactions with an [`InteractionHandler`].
+///
+/// Created with [`InteractionHandler::into_service`]. Clones share the same handler.
+pub struct InteractionService {
+    handler: Arc<InteractionHandler>,
+}
+
+impl<B> Service<Request<B>> for InteractionService
+where
+    B: Body + Send + 'static,
+    B::Data: Send,
+    B::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    type Response = Response<String>;
+    type Error = Infallible;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Infallible>> + Send>>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        let handler = self.handler.clone();
+
+        Box::pin(async move {
+            let (parts, body) = req.into_parts();
+
+            let body = match Limited::new(body, BODY_LIMIT).collect().await {
+                Ok(b) => b.to_bytes(),
+                Err(e) => {
+                    debug!("Failed to read interaction body: {}", e);
+                    let (status, body) = ERROR_RESPONSE!(400, "Failed to read body");
+                    return Ok(response(status, body));
+                }
+            };
+            let body = match String::from_utf8(body.to_vec()) {
+                Ok(b) => b,
+                Err(_) => {
+                    debug!("Incoming interaction rejected, body is not UTF-8");
+                    let (status, body) = ERROR_RESPONSE!(400, "Bad body: not UTF-8");
+                    return Ok(response(status, body));
+                }
+            };
+
+            let (status, body) = handler.process(&parts.headers, &body).await;
+            Ok(response(status, body))
+        })
+    }
+}
+
+fn response(status: u16, body: String) -> Response<String> {
+    let mut builder = Response::builder().status(status);
+    if !body.is_empty() {
+        builder = builder.header(CONTENT_TYPE, "application/json");
+    }
+    builder.body(body).unwrap()
+}
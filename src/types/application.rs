@@ -6,7 +6,11 @@ use serde_with::*;
 #[cfg(feature = "builder")]
 use crate::Builder;
 
+use super::channel::PartialChannel;
 use super::components::ComponentType;
+use super::guild::Role;
+use super::id::{ApplicationId, AttachmentId, ChannelId, CommandId, GuildId, RoleId, UserId};
+use super::modal::ModalSubmitComponent;
 use super::user::*;
 use super::Snowflake;
 use serde_repr::*;
@@ -17,22 +21,16 @@ use crate::types::attachment::Attachment;
 #[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
 /// AKA a 'slash command'.
 pub struct ApplicationCommand {
-    #[serde_as(as = "Option<DisplayFromStr>")]
-    #[serde(default)]
     /// ID of command
-    pub id: Option<Snowflake>,
+    pub id: Option<CommandId>,
 
     /// the type of command, defaults `1` if not set
     pub r#type: Option<ApplicationCommandType>,
 
-    #[serde_as(as = "Option<DisplayFromStr>")]
-    #[serde(default)]
-    application_id: Option<Snowflake>,
+    application_id: Option<ApplicationId>,
 
-    #[serde_as(as = "Option<DisplayFromStr>")]
-    #[serde(default)]
     /// guild id of the command, if not global
-    pub guild_id: Option<Snowflake>,
+    pub guild_id: Option<GuildId>,
 
     /// Command name
     pub name: String,
@@ -88,7 +86,7 @@ pub enum ApplicationCommandPermissionType {
 /// Used for specifying a batch of [`ApplicationCommandPermission`]s
 pub struct ApplicationCommandPermissionBatch {
     /// ID of the command
-    pub id: Snowflake,
+    pub id: CommandId,
     /// Permissions (see [`ApplicationCommandPermission`])
     pub permissions: Vec<ApplicationCommandPermission>,
 }
@@ -242,14 +240,15 @@ pub struct ApplicationCommandOptionChoice {
 /// Stripped down version of ResolvedData
 pub struct ResolvedData {
     /// User map
-    #[serde_as(as = "Option<HashMap<DisplayFromStr, _>>")]
-    pub users: Option<HashMap<Snowflake, User>>,
+    pub users: Option<HashMap<UserId, User>>,
     /// Member map
-    #[serde_as(as = "Option<HashMap<DisplayFromStr, _>>")]
-    pub members: Option<HashMap<Snowflake, Member>>,
+    pub members: Option<HashMap<UserId, PartialMember>>,
 
-    #[serde_as(as = "Option<HashMap<DisplayFromStr, _>>")]
-    pub attachments: Option<HashMap<Snowflake, Attachment>>
+    pub attachments: Option<HashMap<AttachmentId, Attachment>>,
+    /// Role map
+    pub roles: Option<HashMap<RoleId, Role>>,
+    /// Channel map
+    pub channels: Option<HashMap<ChannelId, PartialChannel>>,
 }
 
 #[serde_as]
@@ -257,9 +256,7 @@ pub struct ResolvedData {
 /// Representing a slash command
 pub struct ApplicationCommandInteractionData {
     /// The unique id of the command
-    #[serde_as(as = "Option<DisplayFromStr>")]
-    #[serde(default)]
-    pub id: Option<Snowflake>,
+    pub id: Option<CommandId>,
     /// The name of the command
     pub name: Option<String>,
 
@@ -280,7 +277,10 @@ pub struct ApplicationCommandInteractionData {
     pub custom_id: Option<String>,
 
     /// For Select Menus, the selected values
-    pub values: Option<Vec<ApplicationCommandOption>>,
+    pub values: Option<Vec<String>>,
+
+    /// For Modal submits, the submitted components
+    pub components: Option<Vec<ModalSubmitComponent>>,
 
     #[serde_as(as = "Option<DisplayFromStr>")]
     #[serde(default)]
@@ -288,13 +288,75 @@ pub struct ApplicationCommandInteractionData {
     pub target_id: Option<Snowflake>,
 }
 
+impl ApplicationCommandInteractionData {
+    /// Get the options the user filled in. If a subcommand was invoked, the options of that subcommand are returned.
+    pub fn leaf_options(&self) -> &[ApplicationCommandInteractionDataOption] {
+        let mut options = self.options.as_deref().unwrap_or_default();
+
+        while let [sub] = options {
+            match sub.r#type {
+                ApplicationCommandOptionType::SubCommand
+                | ApplicationCommandOptionType::SubCommandGroup => {
+                    options = sub.options.as_deref().unwrap_or_default();
+                }
+                _ => break,
+            }
+        }
+        options
+    }
+
+    /// Deserialize the options the user filled in into `T`, using the option names as field names.
+    ///
+    /// User, channel, role and mentionable options hold the ID as a string, so they can be deserialized into typed IDs.
+    /// Optional command options should be `Option` fields, since Discord leaves them out if not filled in.
+    ///
+    /// # Example
+    /// ```ignore
+    /// #[derive(Deserialize)]
+    /// struct BanArgs {
+    ///     user: UserId,
+    ///     reason: Option<String>,
+    /// }
+    ///
+    /// let args: BanArgs = data.deserialize_options()?;
+    /// ```
+    pub fn deserialize_options<T: serde::de::DeserializeOwned>(&self) -> Result<T, serde_json::Error> {
+        let map = self
+            .leaf_options()
+            .iter()
+            .filter_map(|o| Some((o.name.clone(), o.value.clone()?)))
+            .collect::<serde_json::Map<_, _>>();
+
+        serde_json::from_value(serde_json::Value::Object(map))
+    }
+
+    /// For Select Menus, get the selected values.
+    ///
+    /// Empty if this is not a select menu interaction.
+    pub fn selected_values(&self) -> &[String] {
+        self.values.as_deref().unwrap_or_default()
+    }
+
+    /// For Modal submits, get the value of the text input with the given `custom_id`
+    pub fn modal_value(&self, custom_id: &str) -> Option<&str> {
+        self.components
+            .as_ref()?
+            .iter()
+            .find_map(|c| c.find_value(custom_id))
+    }
+}
+
 #[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
 /// Representing a bunch of options for slash commands
 pub struct ApplicationCommandInteractionDataOption {
     /// Name of the option
     pub name: String,
-    /// Value of the option
-    pub value: String,
+    /// Type of the option
+    pub r#type: ApplicationCommandOptionType,
+    /// Value of the option. A string, integer, boolean or number depending on the type.
+    ///
+    /// `None` for subcommands and subcommand groups
+    pub value: Option<serde_json::Value>,
     /// More options
     pub options: Option<Vec<ApplicationCommandInteractionDataOption>>,
 }
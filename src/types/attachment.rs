@@ -1,14 +1,13 @@
 use serde::{Deserialize, Serialize};
 use serde_with::*;
 
-use crate::types::Snowflake;
+use crate::types::id::AttachmentId;
 
 #[serde_as]
 #[skip_serializing_none]
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct Attachment {
-    #[serde_as(as = "DisplayFromStr")]
-    pub id: Snowflake,
+    pub id: AttachmentId,
     pub filename: String,
     pub description: Option<String>,
     pub content_type: Option<String>,
@@ -0,0 +1,196 @@
+use super::guild::Guild;
+use super::id::{GuildId, UserId};
+use super::user::{Member, User};
+
+/// Base URL of Discord's CDN
+pub const CDN_URL: &str = "https://cdn.discordapp.com";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// The file format of an image on the CDN
+pub enum ImageFormat {
+    /// `.png`
+    Png,
+    /// `.jpg`
+    Jpeg,
+    /// `.webp`
+    WebP,
+    /// `.gif`. Only available for animated images, static images fall back to png
+    Gif,
+}
+
+impl ImageFormat {
+    /// The file extension for this format
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ImageFormat::Png => "png",
+            ImageFormat::Jpeg => "jpg",
+            ImageFormat::WebP => "webp",
+            ImageFormat::Gif => "gif",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+/// Options for an image URL.
+///
+/// By default, animated images (hashes starting with `a_`) are returned as gif and others as png, in their original size.
+pub struct ImageOptions {
+    format: Option<ImageFormat>,
+    size: Option<u16>,
+}
+
+impl ImageOptions {
+    /// Request the image in this format
+    pub fn format(mut self, format: ImageFormat) -> Self {
+        self.format = Some(format);
+        self
+    }
+
+    /// Request the image in this size.
+    ///
+    /// The CDN only accepts powers of two between 16 and 4096, other sizes are rounded up to the next accepted size.
+    pub fn size(mut self, size: u16) -> Self {
+        self.size = Some(size.clamp(16, 4096).next_power_of_two());
+        self
+    }
+}
+
+/// Whether the image with this hash is animated
+pub fn is_animated(hash: &str) -> bool {
+    hash.starts_with("a_")
+}
+
+fn image_url(path: &str, hash: &str, opts: ImageOptions) -> String {
+    let format = match opts.format {
+        // Static images are not available as gif
+        Some(ImageFormat::Gif) | None if !is_animated(hash) => ImageFormat::Png,
+        Some(f) => f,
+        None => ImageFormat::Gif,
+    };
+
+    let mut url = format!("{}/{}/{}.{}", CDN_URL, path, hash, format.extension());
+    if let Some(size) = opts.size {
+        url.push_str(&format!("?size={}", size));
+    }
+    url
+}
+
+/// URL of a user's avatar
+pub fn user_avatar(user_id: UserId, hash: &str, opts: ImageOptions) -> String {
+    image_url(&format!("avatars/{}", user_id), hash, opts)
+}
+
+/// URL of the default avatar of a user without a custom avatar.
+///
+/// Users on the legacy username system (with a discriminator other than `0`) have a different default avatar.
+pub fn default_avatar(user_id: UserId, discriminator: Option<&str>) -> String {
+    let index = match discriminator.and_then(|d| d.parse::<u16>().ok()) {
+        Some(d) if d != 0 => u64::from(d % 5),
+        _ => (user_id.get() >> 22) % 6,
+    };
+    format!("{}/embed/avatars/{}.png", CDN_URL, index)
+}
+
+/// URL of a member's guild-specific avatar
+pub fn member_avatar(guild_id: GuildId, user_id: UserId, hash: &str, opts: ImageOptions) -> String {
+    image_url(
+        &format!("guilds/{}/users/{}/avatars", guild_id, user_id),
+        hash,
+        opts,
+    )
+}
+
+/// URL of a user's profile banner
+pub fn user_banner(user_id: UserId, hash: &str, opts: ImageOptions) -> String {
+    image_url(&format!("banners/{}", user_id), hash, opts)
+}
+
+/// URL of a guild's icon
+pub fn guild_icon(guild_id: GuildId, hash: &str, opts: ImageOptions) -> String {
+    image_url(&format!("icons/{}", guild_id), hash, opts)
+}
+
+/// URL of a guild's invite splash
+pub fn guild_splash(guild_id: GuildId, hash: &str, opts: ImageOptions) -> String {
+    image_url(&format!("splashes/{}", guild_id), hash, opts)
+}
+
+/// URL of a guild's discovery splash
+pub fn guild_discovery_splash(guild_id: GuildId, hash: &str, opts: ImageOptions) -> String {
+    image_url(&format!("discovery-splashes/{}", guild_id), hash, opts)
+}
+
+/// URL of a guild's banner
+pub fn guild_banner(guild_id: GuildId, hash: &str, opts: ImageOptions) -> String {
+    image_url(&format!("banners/{}", guild_id), hash, opts)
+}
+
+impl User {
+    /// URL of this user's avatar, if they set one
+    pub fn avatar_url(&self, opts: ImageOptions) -> Option<String> {
+        self.avatar
+            .as_deref()
+            .map(|h| user_avatar(self.id, h, opts))
+    }
+
+    /// URL of the avatar Discord shows for this user if they didn't set one
+    pub fn default_avatar_url(&self) -> String {
+        default_avatar(self.id, self.discriminator.as_deref())
+    }
+
+    /// URL of the avatar shown in the client: the user's avatar, or the default avatar if they didn't set one
+    pub fn display_avatar_url(&self, opts: ImageOptions) -> String {
+        self.avatar_url(opts)
+            .unwrap_or_else(|| self.default_avatar_url())
+    }
+
+    /// URL of this user's profile banner, if they set one
+    pub fn banner_url(&self, opts: ImageOptions) -> Option<String> {
+        self.banner
+            .as_deref()
+            .map(|h| user_banner(self.id, h, opts))
+    }
+}
+
+impl Member {
+    /// URL of this member's guild-specific avatar, if they set one
+    pub fn avatar_url(&self, guild_id: GuildId, opts: ImageOptions) -> Option<String> {
+        self.avatar
+            .as_deref()
+            .map(|h| member_avatar(guild_id, self.user.id, h, opts))
+    }
+
+    /// URL of the avatar shown in the guild: the guild avatar, the user's avatar, or the default avatar
+    pub fn display_avatar_url(&self, guild_id: GuildId, opts: ImageOptions) -> String {
+        self.avatar_url(guild_id, opts)
+            .unwrap_or_else(|| self.user.display_avatar_url(opts))
+    }
+}
+
+impl Guild {
+    /// URL of this guild's icon, if it has one
+    pub fn icon_url(&self, opts: ImageOptions) -> Option<String> {
+        self.icon.as_deref().map(|h| guild_icon(self.id, h, opts))
+    }
+
+    /// URL of this guild's invite splash, if it has one
+    pub fn splash_url(&self, opts: ImageOptions) -> Option<String> {
+        self.splash
+            .as_deref()
+            .map(|h| guild_splash(self.id, h, opts))
+    }
+
+    /// URL of this guild's discovery splash, if it has one
+    pub fn discovery_splash_url(&self, opts: ImageOptions) -> Option<String> {
+        self.discovery_splash
+            .as_deref()
+            .map(|h| guild_discovery_splash(self.id, h, opts))
+    }
+
+    /// URL of this guild's banner, if it has one
+    pub fn banner_url(&self, opts: ImageOptions) -> Option<String> {
+        self.banner
+            .as_deref()
+            .map(|h| guild_banner(self.id, h, opts))
+    }
+}
@@ -0,0 +1,61 @@
+use serde::{Deserialize, Serialize};
+use serde_repr::*;
+use serde_with::*;
+
+use super::id::ChannelId;
+
+#[derive(Clone, Copy, Serialize_repr, Deserialize_repr, PartialEq, Eq, Debug)]
+#[repr(u8)]
+#[non_exhaustive]
+/// Represents the type of a channel
+pub enum ChannelType {
+    /// A text channel within a guild
+    GuildText = 0,
+    /// A direct message between users
+    Dm = 1,
+    /// A voice channel within a guild
+    GuildVoice = 2,
+    /// A direct message between multiple users
+    GroupDm = 3,
+    /// An organizational category that contains up to 50 channels
+    GuildCategory = 4,
+    /// A channel that users can follow and crosspost into their own guild
+    GuildAnnouncement = 5,
+    /// A temporary sub-channel within a `GuildAnnouncement` channel
+    AnnouncementThread = 10,
+    /// A temporary sub-channel within a `GuildText` or `GuildForum` channel
+    PublicThread = 11,
+    /// A temporary sub-channel only viewable by those invited and those with the `MANAGE_THREADS` permission
+    PrivateThread = 12,
+    /// A voice channel for hosting events with an audience
+    GuildStageVoice = 13,
+    /// The channel in a hub containing the listed servers
+    GuildDirectory = 14,
+    /// A channel that can only contain threads
+    GuildForum = 15,
+    /// A channel that can only contain threads, similar to `GuildForum`
+    GuildMedia = 16,
+}
+
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Clone, Serialize, Deserialize, Debug)]
+/// A partial channel, as found in resolved interaction data
+pub struct PartialChannel {
+    /// The ID of the channel
+    pub id: ChannelId,
+    /// The type of channel
+    pub r#type: ChannelType,
+    /// The name of the channel
+    pub name: Option<String>,
+    /// Computed permissions for the invoking user in the channel, including overwrites
+    pub permissions: Option<String>,
+    /// For guild channels the id of the parent category, for threads the id of the parent channel
+    pub parent_id: Option<ChannelId>,
+}
+
+impl PartialEq for PartialChannel {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
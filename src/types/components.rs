@@ -10,6 +10,9 @@ use serde::{Deserialize, Serialize};
 #[cfg(feature = "builder")]
 use crate::Builder;
 
+use super::channel::ChannelType;
+use super::id::{ChannelId, RoleId, SkuId, UserId};
+use super::Snowflake;
 use serde_repr::*;
 use serde_with::*;
 
@@ -36,6 +39,23 @@ pub struct MessageComponent {
     max_length: Option<u16>,
     required: Option<bool>,
     value: Option<String>,
+
+    default_values: Option<Vec<SelectDefaultValue>>,
+    channel_types: Option<Vec<ChannelType>>,
+
+    sku_id: Option<SkuId>,
+
+    id: Option<u32>,
+    content: Option<String>,
+    accessory: Option<Box<MessageComponent>>,
+    media: Option<UnfurledMediaItem>,
+    description: Option<String>,
+    spoiler: Option<bool>,
+    items: Option<Vec<MediaGalleryItem>>,
+    file: Option<UnfurledMediaItem>,
+    divider: Option<bool>,
+    spacing: Option<SeparatorSpacing>,
+    accent_color: Option<u32>,
 }
 
 impl Default for MessageComponent {
@@ -57,10 +77,59 @@ impl Default for MessageComponent {
             max_length: None,
             required: None,
             value: None,
+            default_values: None,
+            channel_types: None,
+            sku_id: None,
+            id: None,
+            content: None,
+            accessory: None,
+            media: None,
+            description: None,
+            spoiler: None,
+            items: None,
+            file: None,
+            divider: None,
+            spacing: None,
+            accent_color: None,
         }
     }
 }
 
+impl MessageComponent {
+    /// Get the type of this component
+    pub fn component_type(&self) -> &ComponentType {
+        &self.r#type
+    }
+
+    /// Get the child components of this component (e.g. the buttons in an action row)
+    pub fn components(&self) -> &[MessageComponent] {
+        self.components.as_deref().unwrap_or_default()
+    }
+
+    /// Get the accessory of this component. Only set on sections.
+    pub fn accessory(&self) -> Option<&MessageComponent> {
+        self.accessory.as_deref()
+    }
+
+    /// Get the text of this component. Only set on text displays.
+    pub fn content(&self) -> Option<&str> {
+        self.content.as_deref()
+    }
+
+    /// Sets the optional identifier of this component. Must be unique within the message.
+    pub fn set_id(mut self, id: u32) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    #[cfg(feature = "handler-core")]
+    /// Counts this component and all components nested in it
+    pub(crate) fn count(&self) -> usize {
+        1 + self.components().iter().map(|c| c.count()).sum::<usize>()
+            + self.accessory().map_or(0, |a| a.count())
+    }
+}
+
 #[derive(Clone, Serialize_repr, Deserialize_repr, PartialEq, Debug)]
 #[repr(u8)]
 #[non_exhaustive]
@@ -74,6 +143,97 @@ pub enum ComponentType {
     SelectMenu = 3,
     /// A text input object
     TextInput = 4,
+    /// A select menu for users
+    UserSelect = 5,
+    /// A select menu for roles
+    RoleSelect = 6,
+    /// A select menu for mentionables (users and roles)
+    MentionableSelect = 7,
+    /// A select menu for channels
+    ChannelSelect = 8,
+    /// Text displayed next to an accessory
+    Section = 9,
+    /// Markdown text
+    TextDisplay = 10,
+    /// A small image, used as an accessory of a section
+    Thumbnail = 11,
+    /// A gallery of images and videos
+    MediaGallery = 12,
+    /// An uploaded file
+    File = 13,
+    /// Vertical padding between other components
+    Separator = 14,
+    /// A visually grouped set of components, with an optional accent color
+    Container = 17,
+}
+
+impl ComponentType {
+    /// Whether this is a layout component that requires the `IS_COMPONENTS_V2` message flag
+    pub fn is_v2(&self) -> bool {
+        matches!(
+            self,
+            ComponentType::Section
+                | ComponentType::TextDisplay
+                | ComponentType::Thumbnail
+                | ComponentType::MediaGallery
+                | ComponentType::File
+                | ComponentType::Separator
+                | ComponentType::Container
+        )
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+/// A piece of media used by a component. Supports arbitrary URLs and `attachment://<filename>` references.
+pub struct UnfurledMediaItem {
+    /// The URL of the media
+    pub url: String,
+}
+
+impl UnfurledMediaItem {
+    /// Creates a new media item
+    pub fn new(url: impl Into<String>) -> Self {
+        Self { url: url.into() }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+/// An item in a media gallery
+pub struct MediaGalleryItem {
+    media: UnfurledMediaItem,
+    description: Option<String>,
+    spoiler: Option<bool>,
+}
+
+impl MediaGalleryItem {
+    /// Creates a new gallery item from an URL or `attachment://<filename>` reference
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            media: UnfurledMediaItem::new(url),
+            description: None,
+            spoiler: None,
+        }
+    }
+    /// Sets the alt text of this item
+    pub fn description(mut self, des: impl Into<String>) -> Self {
+        self.description = Some(des.into());
+        self
+    }
+    /// Blurs this item as a spoiler
+    pub fn spoiler(mut self, spoiler: bool) -> Self {
+        self.spoiler = Some(spoiler);
+        self
+    }
+}
+
+#[derive(Clone, Copy, Serialize_repr, Deserialize_repr, PartialEq, Eq, Debug)]
+#[repr(u8)]
+/// How much padding a separator adds
+pub enum SeparatorSpacing {
+    /// Small padding (default)
+    Small = 1,
+    /// Large padding
+    Large = 2,
 }
 
 #[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
@@ -157,7 +317,120 @@ impl From<ComponentSelectMenu> for MessageComponent {
     }
 }
 
+#[derive(Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+/// The type of a [`SelectDefaultValue`]
+pub enum SelectDefaultValueType {
+    /// A user
+    User,
+    /// A role
+    Role,
+    /// A channel
+    Channel,
+}
+
+#[serde_as]
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+/// A value that is selected by default in an auto-populated select menu
+pub struct SelectDefaultValue {
+    #[serde_as(as = "DisplayFromStr")]
+    id: Snowflake,
+    r#type: SelectDefaultValueType,
+}
+
+impl SelectDefaultValue {
+    /// Creates a new default value
+    pub fn new(id: impl Into<Snowflake>, t: SelectDefaultValueType) -> Self {
+        Self {
+            id: id.into(),
+            r#type: t,
+        }
+    }
+
+    /// A user that is selected by default
+    pub fn user(id: impl Into<UserId>) -> Self {
+        Self::new(id.into(), SelectDefaultValueType::User)
+    }
+
+    /// A role that is selected by default
+    pub fn role(id: impl Into<RoleId>) -> Self {
+        Self::new(id.into(), SelectDefaultValueType::Role)
+    }
+
+    /// A channel that is selected by default
+    pub fn channel(id: impl Into<ChannelId>) -> Self {
+        Self::new(id.into(), SelectDefaultValueType::Channel)
+    }
+
+    /// Get the ID of this default value
+    pub fn id(&self) -> Snowflake {
+        self.id
+    }
+
+    /// Get the type of this default value
+    pub fn value_type(&self) -> SelectDefaultValueType {
+        self.r#type
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+/// An auto-populated select menu (users, roles, mentionables or channels)
+pub struct ComponentAutoSelectMenu {
+    r#type: ComponentType,
+    custom_id: String,
+    placeholder: Option<String>,
+    default_values: Vec<SelectDefaultValue>,
+    channel_types: Vec<ChannelType>,
+    min_values: u8,
+    max_values: u8,
+    disabled: Option<bool>,
+}
+
+#[cfg(feature = "builder")]
+impl Default for ComponentAutoSelectMenu {
+    fn default() -> Self {
+        Self {
+            r#type: ComponentType::UserSelect,
+            custom_id: String::new(),
+            placeholder: None,
+            default_values: Vec::new(),
+            channel_types: Vec::new(),
+
+            // documented defaults
+            min_values: 1,
+            max_values: 1,
+            disabled: None,
+        }
+    }
+}
+
+#[cfg(feature = "builder")]
+impl From<ComponentAutoSelectMenu> for MessageComponent {
+    fn from(t: ComponentAutoSelectMenu) -> Self {
+        MessageComponent {
+            r#type: t.r#type,
+            custom_id: Some(t.custom_id),
+            placeholder: t.placeholder,
+            min_values: Some(t.min_values),
+            max_values: Some(t.max_values),
+            disabled: t.disabled,
+            default_values: if t.default_values.is_empty() {
+                None
+            } else {
+                Some(t.default_values)
+            },
+            channel_types: if t.channel_types.is_empty() {
+                None
+            } else {
+                Some(t.channel_types)
+            },
+            ..Default::default()
+        }
+    }
+}
+
 #[cfg(feature = "builder")]
+#[serde_as]
 #[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
 /// A button
 pub struct ComponentButton {
@@ -167,6 +440,7 @@ pub struct ComponentButton {
     custom_id: Option<String>,
     url: Option<String>,
     disabled: Option<bool>,
+    sku_id: Option<SkuId>,
 }
 #[cfg(feature = "builder")]
 impl Default for ComponentButton {
@@ -178,6 +452,7 @@ impl Default for ComponentButton {
             custom_id: None,
             url: None,
             disabled: None,
+            sku_id: None,
         }
     }
 }
@@ -192,6 +467,7 @@ impl From<ComponentButton> for MessageComponent {
             custom_id: t.custom_id,
             url: t.url,
             disabled: t.disabled,
+            sku_id: t.sku_id,
             ..Default::default()
         }
     }
@@ -212,6 +488,150 @@ pub enum ComponentButtonStyle {
     Danger = 4,
     /// grey with outgoing link icon
     Link = 5,
+    /// blurple, used to purchase a [`Sku`](crate::types::monetization::Sku)
+    Premium = 6,
+}
+
+#[cfg(feature = "builder")]
+#[derive(Clone, PartialEq, Debug)]
+/// A text display component. Supports markdown.
+pub struct ComponentTextDisplay {
+    content: String,
+}
+
+#[cfg(feature = "builder")]
+impl ComponentTextDisplay {
+    /// Creates a new text display
+    pub fn new(content: impl Into<String>) -> Self {
+        Self {
+            content: content.into(),
+        }
+    }
+}
+
+#[cfg(feature = "builder")]
+impl From<ComponentTextDisplay> for MessageComponent {
+    fn from(t: ComponentTextDisplay) -> Self {
+        MessageComponent {
+            r#type: ComponentType::TextDisplay,
+            content: Some(t.content),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(feature = "builder")]
+#[derive(Clone, PartialEq, Debug)]
+/// A thumbnail, used as the accessory of a section
+pub struct ComponentThumbnail {
+    media: UnfurledMediaItem,
+    description: Option<String>,
+    spoiler: Option<bool>,
+}
+
+#[cfg(feature = "builder")]
+impl ComponentThumbnail {
+    /// Creates a new thumbnail from an URL or `attachment://<filename>` reference
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            media: UnfurledMediaItem::new(url),
+            description: None,
+            spoiler: None,
+        }
+    }
+    /// Sets the alt text of this thumbnail
+    pub fn description(mut self, des: impl Into<String>) -> Self {
+        self.description = Some(des.into());
+        self
+    }
+    /// Blurs this thumbnail as a spoiler
+    pub fn spoiler(mut self, spoiler: bool) -> Self {
+        self.spoiler = Some(spoiler);
+        self
+    }
+}
+
+#[cfg(feature = "builder")]
+impl From<ComponentThumbnail> for MessageComponent {
+    fn from(t: ComponentThumbnail) -> Self {
+        MessageComponent {
+            r#type: ComponentType::Thumbnail,
+            media: Some(t.media),
+            description: t.description,
+            spoiler: t.spoiler,
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(feature = "builder")]
+#[derive(Clone, PartialEq, Debug)]
+/// A file component, displaying an uploaded attachment
+pub struct ComponentFile {
+    file: UnfurledMediaItem,
+    spoiler: Option<bool>,
+}
+
+#[cfg(feature = "builder")]
+impl ComponentFile {
+    /// Creates a new file component referencing the attachment with this file name
+    pub fn attachment(filename: impl std::fmt::Display) -> Self {
+        Self {
+            file: UnfurledMediaItem::new(format!("attachment://{}", filename)),
+            spoiler: None,
+        }
+    }
+    /// Blurs this file as a spoiler
+    pub fn spoiler(mut self, spoiler: bool) -> Self {
+        self.spoiler = Some(spoiler);
+        self
+    }
+}
+
+#[cfg(feature = "builder")]
+impl From<ComponentFile> for MessageComponent {
+    fn from(t: ComponentFile) -> Self {
+        MessageComponent {
+            r#type: ComponentType::File,
+            file: Some(t.file),
+            spoiler: t.spoiler,
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(feature = "builder")]
+#[derive(Clone, Default, PartialEq, Debug)]
+/// A separator, adding vertical padding (and optionally a divider line) between components
+pub struct ComponentSeparator {
+    divider: Option<bool>,
+    spacing: Option<SeparatorSpacing>,
+}
+
+#[cfg(feature = "builder")]
+impl ComponentSeparator {
+    /// Whether a visual divider should be displayed; default true
+    pub fn divider(mut self, divider: bool) -> Self {
+        self.divider = Some(divider);
+        self
+    }
+    /// Sets the size of the padding
+    pub fn spacing(mut self, spacing: SeparatorSpacing) -> Self {
+        self.spacing = Some(spacing);
+        self
+    }
+}
+
+#[cfg(feature = "builder")]
+impl From<ComponentSeparator> for MessageComponent {
+    fn from(t: ComponentSeparator) -> Self {
+        MessageComponent {
+            r#type: ComponentType::Separator,
+            divider: t.divider,
+            spacing: t.spacing,
+            ..Default::default()
+        }
+    }
 }
 
 /// Builder for creating a Component Action Row
@@ -245,8 +665,10 @@ impl ComponentRowBuilder {
         self
     }
 
-    /// Add a select menu to the row
-    pub fn add_select_menu(mut self, menu: ComponentSelectMenu) -> Self {
+    /// Add a select menu to the row.
+    ///
+    /// This takes both a [`ComponentSelectMenu`] and a [`ComponentAutoSelectMenu`]
+    pub fn add_select_menu(mut self, menu: impl Into<MessageComponent>) -> Self {
         match self.obj.components.as_mut() {
             None => {
                 self.obj.components = Some(vec![menu.into()]);
@@ -290,6 +712,11 @@ impl ComponentButtonBuilder {
                     warn!("The button style is set to 'Link', but no url was specified.");
                 }
             }
+            ComponentButtonStyle::Premium => {
+                if self.obj.sku_id.is_none() {
+                    warn!("The button style is set to 'Premium', but no sku_id was specified.");
+                }
+            }
             _ => {
                 if self.obj.custom_id.is_none() {
                     warn!("No custom_id was supplied for this button!")
@@ -330,6 +757,11 @@ impl ComponentButtonBuilder {
         self.obj.disabled = Some(disabled);
         self
     }
+    /// Set the ID of the SKU to purchase (required if style is set to `ComponentButtonStyle::Premium`)
+    pub fn sku_id(mut self, id: impl Into<SkuId>) -> Self {
+        self.obj.sku_id = Some(id.into());
+        self
+    }
 }
 
 #[cfg(feature = "builder")]
@@ -342,6 +774,10 @@ pub enum ComponentBuilderError {
     LinkWithoutUrl,
     /// The component was a Button without a specified custom ID
     NoCustomId,
+    /// The component was a Premium button without a specified SKU ID
+    PremiumWithoutSku,
+    /// The component was a Premium button with a custom ID, label, url or emoji
+    PremiumWithContent,
 }
 
 #[cfg(feature = "builder")]
@@ -356,6 +792,14 @@ impl Display for ComponentBuilderError {
             ComponentBuilderError::NoCustomId => {
                 write!(f, "no custom ID specified for this button")
             }
+            ComponentBuilderError::PremiumWithoutSku => write!(
+                f,
+                "the button style is set to 'Premium', but no sku_id was specified"
+            ),
+            ComponentBuilderError::PremiumWithContent => write!(
+                f,
+                "premium buttons cannot have a custom ID, label, url or emoji"
+            ),
         }
     }
 }
@@ -380,6 +824,18 @@ impl Builder<ComponentButton> for ComponentButtonBuilder {
                     return Err(ComponentBuilderError::LinkWithoutUrl);
                 }
             }
+            ComponentButtonStyle::Premium => {
+                if self.obj.sku_id.is_none() {
+                    return Err(ComponentBuilderError::PremiumWithoutSku);
+                }
+                if self.obj.custom_id.is_some()
+                    || self.obj.label.is_some()
+                    || self.obj.url.is_some()
+                    || self.obj.emoji.is_some()
+                {
+                    return Err(ComponentBuilderError::PremiumWithContent);
+                }
+            }
             _ => {
                 if self.obj.custom_id.is_none() {
                     return Err(ComponentBuilderError::NoCustomId);
@@ -495,3 +951,388 @@ impl Builder<ComponentSelectMenu> for ComponentSelectMenuBuilder {
         Ok(self.obj)
     }
 }
+
+#[cfg(feature = "builder")]
+#[derive(Clone, Debug, Default)]
+/// Builder pattern for creating auto-populated select menus (users, roles, mentionables and channels).
+pub struct ComponentAutoSelectMenuBuilder {
+    obj: ComponentAutoSelectMenu,
+}
+
+#[cfg(feature = "builder")]
+impl ComponentAutoSelectMenuBuilder {
+    /// Sets the kind of select menu. Must be one of `UserSelect`, `RoleSelect`, `MentionableSelect` or `ChannelSelect`.
+    ///
+    /// Defaults to `UserSelect`
+    pub fn menu_type(mut self, t: &ComponentType) -> Self {
+        self.obj.r#type = t.clone();
+        self
+    }
+
+    /// The custom developer identifier. **SETTING THIS IS MANDATORY!**
+    pub fn custom_id(mut self, id: impl Into<String>) -> Self {
+        self.obj.custom_id = id.into();
+        self
+    }
+
+    /// custom placeholder text if nothing is selected, max 150 characters
+    pub fn placeholder(mut self, ph: impl Into<String>) -> Self {
+        self.obj.placeholder = Some(ph.into());
+        self
+    }
+
+    /// Add a value that is selected by default
+    pub fn add_default_value(mut self, value: SelectDefaultValue) -> Self {
+        self.obj.default_values.push(value);
+        self
+    }
+
+    /// Restrict the channel types that can be picked. Only applicable to `ChannelSelect` menus
+    pub fn add_channel_type(mut self, t: ChannelType) -> Self {
+        self.obj.channel_types.push(t);
+        self
+    }
+
+    /// The minimum number of items that must be chosen; default 1, min 0, max 25
+    pub fn min_values(mut self, min: impl Into<u8>) -> Self {
+        self.obj.min_values = min.into();
+        self
+    }
+
+    /// the maximum number of items that can be chosen; default 1, max 25
+    pub fn max_values(mut self, max: impl Into<u8>) -> Self {
+        self.obj.max_values = max.into();
+        self
+    }
+
+    /// Disables/deactivates the menu
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.obj.disabled = Some(disabled);
+        self
+    }
+}
+
+#[cfg(feature = "builder")]
+#[derive(Debug)]
+/// Represents an error that occurred when building a ComponentAutoSelectMenu
+pub enum ComponentAutoSelectMenuBuilderError {
+    /// The menu type is not an auto-populated select menu
+    NotAutoSelectMenu,
+    /// There was no Custom ID supplied with this menu
+    EmptyCustomId,
+    /// The placeholder exceeded 150 characters
+    PlaceholderTooLong,
+    /// There were over 25 min_values supplied for this menu
+    Over25MinValues,
+    /// There were over 25 max_values supplied for this menu
+    Over25MaxValues,
+    /// `min_values` is greater than `max_values`
+    MinValuesOverMaxValues,
+    /// The amount of default values is outside of the `min_values`/`max_values` range
+    DefaultValuesOutOfRange,
+    /// A default value's type does not match the type of the menu
+    DefaultValueTypeMismatch,
+    /// Channel types were set on a menu that is not a `ChannelSelect`
+    ChannelTypesOnNonChannelSelect,
+}
+
+#[cfg(feature = "builder")]
+impl Display for ComponentAutoSelectMenuBuilderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ComponentAutoSelectMenuBuilderError::NotAutoSelectMenu => {
+                write!(f, "menu type is not an auto-populated select menu")
+            }
+            ComponentAutoSelectMenuBuilderError::EmptyCustomId => write!(f, "custom_id is empty"),
+            ComponentAutoSelectMenuBuilderError::PlaceholderTooLong => {
+                write!(f, "placeholder exceeded 150 characters")
+            }
+            ComponentAutoSelectMenuBuilderError::Over25MinValues => {
+                write!(f, "over 25 min_values options supplied")
+            }
+            ComponentAutoSelectMenuBuilderError::Over25MaxValues => {
+                write!(f, "over 25 max_values options supplied")
+            }
+            ComponentAutoSelectMenuBuilderError::MinValuesOverMaxValues => {
+                write!(f, "min_values is greater than max_values")
+            }
+            ComponentAutoSelectMenuBuilderError::DefaultValuesOutOfRange => {
+                write!(
+                    f,
+                    "amount of default values is outside min_values/max_values"
+                )
+            }
+            ComponentAutoSelectMenuBuilderError::DefaultValueTypeMismatch => {
+                write!(f, "default value type does not match the menu type")
+            }
+            ComponentAutoSelectMenuBuilderError::ChannelTypesOnNonChannelSelect => {
+                write!(f, "channel types can only be set on channel select menus")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "builder")]
+impl error::Error for ComponentAutoSelectMenuBuilderError {}
+
+#[cfg(feature = "builder")]
+impl Builder<ComponentAutoSelectMenu> for ComponentAutoSelectMenuBuilder {
+    type Error = ComponentAutoSelectMenuBuilderError;
+
+    fn build(self) -> Result<ComponentAutoSelectMenu, Self::Error> {
+        let allowed: &[SelectDefaultValueType] = match self.obj.r#type {
+            ComponentType::UserSelect => &[SelectDefaultValueType::User],
+            ComponentType::RoleSelect => &[SelectDefaultValueType::Role],
+            ComponentType::MentionableSelect => {
+                &[SelectDefaultValueType::User, SelectDefaultValueType::Role]
+            }
+            ComponentType::ChannelSelect => &[SelectDefaultValueType::Channel],
+            _ => return Err(ComponentAutoSelectMenuBuilderError::NotAutoSelectMenu),
+        };
+
+        if self.obj.custom_id.is_empty() {
+            return Err(ComponentAutoSelectMenuBuilderError::EmptyCustomId);
+        }
+        if let Some(p) = &self.obj.placeholder {
+            if p.chars().count() > 150 {
+                return Err(ComponentAutoSelectMenuBuilderError::PlaceholderTooLong);
+            }
+        }
+        if self.obj.min_values > 25 {
+            return Err(ComponentAutoSelectMenuBuilderError::Over25MinValues);
+        }
+        if self.obj.max_values > 25 {
+            return Err(ComponentAutoSelectMenuBuilderError::Over25MaxValues);
+        }
+        if self.obj.min_values > self.obj.max_values {
+            return Err(ComponentAutoSelectMenuBuilderError::MinValuesOverMaxValues);
+        }
+        if !self.obj.default_values.is_empty() {
+            let len = self.obj.default_values.len();
+            if len < self.obj.min_values as usize || len > self.obj.max_values as usize {
+                return Err(ComponentAutoSelectMenuBuilderError::DefaultValuesOutOfRange);
+            }
+        }
+        if self
+            .obj
+            .default_values
+            .iter()
+            .any(|v| !allowed.contains(&v.r#type))
+        {
+            return Err(ComponentAutoSelectMenuBuilderError::DefaultValueTypeMismatch);
+        }
+        if !self.obj.channel_types.is_empty() && self.obj.r#type != ComponentType::ChannelSelect {
+            return Err(ComponentAutoSelectMenuBuilderError::ChannelTypesOnNonChannelSelect);
+        }
+        Ok(self.obj)
+    }
+}
+
+#[cfg(feature = "builder")]
+#[derive(Debug, Clone, PartialEq)]
+/// An error that occurred when building a layout component (section, media gallery or container)
+pub enum ComponentLayoutBuilderError {
+    /// A section must contain 1 to 3 text displays
+    SectionTextCount,
+    /// A section must have a button or thumbnail as accessory
+    SectionWithoutAccessory,
+    /// A media gallery must contain 1 to 10 items
+    MediaGalleryItemCount,
+    /// A container must contain at least one component
+    EmptyContainer,
+    /// The component at this index cannot be placed in a container
+    InvalidContainerChild(usize),
+}
+
+#[cfg(feature = "builder")]
+impl Display for ComponentLayoutBuilderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ComponentLayoutBuilderError::SectionTextCount => {
+                write!(f, "a section must contain 1 to 3 text displays")
+            }
+            ComponentLayoutBuilderError::SectionWithoutAccessory => {
+                write!(f, "no accessory specified for this section")
+            }
+            ComponentLayoutBuilderError::MediaGalleryItemCount => {
+                write!(f, "a media gallery must contain 1 to 10 items")
+            }
+            ComponentLayoutBuilderError::EmptyContainer => write!(f, "container is empty"),
+            ComponentLayoutBuilderError::InvalidContainerChild(i) => {
+                write!(f, "component {} cannot be placed in a container", i)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "builder")]
+impl error::Error for ComponentLayoutBuilderError {}
+
+#[cfg(feature = "builder")]
+#[derive(Clone, PartialEq, Debug)]
+/// Builder for creating a section: up to 3 text displays next to a button or thumbnail
+pub struct ComponentSectionBuilder {
+    obj: MessageComponent,
+}
+
+#[cfg(feature = "builder")]
+impl Default for ComponentSectionBuilder {
+    fn default() -> Self {
+        Self {
+            obj: MessageComponent {
+                r#type: ComponentType::Section,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+#[cfg(feature = "builder")]
+impl ComponentSectionBuilder {
+    /// Add a text display to the section
+    pub fn add_text(mut self, text: ComponentTextDisplay) -> Self {
+        self.obj
+            .components
+            .get_or_insert_with(Vec::new)
+            .push(text.into());
+        self
+    }
+
+    /// Set a button as accessory of the section
+    pub fn button_accessory(mut self, button: ComponentButton) -> Self {
+        self.obj.accessory = Some(Box::new(button.into()));
+        self
+    }
+
+    /// Set a thumbnail as accessory of the section
+    pub fn thumbnail_accessory(mut self, thumbnail: ComponentThumbnail) -> Self {
+        self.obj.accessory = Some(Box::new(thumbnail.into()));
+        self
+    }
+}
+
+#[cfg(feature = "builder")]
+impl Builder<MessageComponent> for ComponentSectionBuilder {
+    type Error = ComponentLayoutBuilderError;
+
+    fn build(self) -> Result<MessageComponent, Self::Error> {
+        let texts = self.obj.components().len();
+        if texts == 0 || texts > 3 {
+            return Err(ComponentLayoutBuilderError::SectionTextCount);
+        }
+        if self.obj.accessory.is_none() {
+            return Err(ComponentLayoutBuilderError::SectionWithoutAccessory);
+        }
+        Ok(self.obj)
+    }
+}
+
+#[cfg(feature = "builder")]
+#[derive(Clone, PartialEq, Debug)]
+/// Builder for creating a media gallery
+pub struct ComponentMediaGalleryBuilder {
+    obj: MessageComponent,
+}
+
+#[cfg(feature = "builder")]
+impl Default for ComponentMediaGalleryBuilder {
+    fn default() -> Self {
+        Self {
+            obj: MessageComponent {
+                r#type: ComponentType::MediaGallery,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+#[cfg(feature = "builder")]
+impl ComponentMediaGalleryBuilder {
+    /// Add an item to the gallery. A gallery holds up to 10 items.
+    pub fn add_item(mut self, item: MediaGalleryItem) -> Self {
+        self.obj.items.get_or_insert_with(Vec::new).push(item);
+        self
+    }
+}
+
+#[cfg(feature = "builder")]
+impl Builder<MessageComponent> for ComponentMediaGalleryBuilder {
+    type Error = ComponentLayoutBuilderError;
+
+    fn build(self) -> Result<MessageComponent, Self::Error> {
+        let items = self.obj.items.as_ref().map_or(0, |i| i.len());
+        if items == 0 || items > 10 {
+            return Err(ComponentLayoutBuilderError::MediaGalleryItemCount);
+        }
+        Ok(self.obj)
+    }
+}
+
+#[cfg(feature = "builder")]
+#[derive(Clone, PartialEq, Debug)]
+/// Builder for creating a container
+pub struct ComponentContainerBuilder {
+    obj: MessageComponent,
+}
+
+#[cfg(feature = "builder")]
+impl Default for ComponentContainerBuilder {
+    fn default() -> Self {
+        Self {
+            obj: MessageComponent {
+                r#type: ComponentType::Container,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+#[cfg(feature = "builder")]
+impl ComponentContainerBuilder {
+    /// Add a component to the container.
+    ///
+    /// This can be an action row, text display, section, media gallery, separator or file.
+    pub fn add_component(mut self, comp: impl Into<MessageComponent>) -> Self {
+        self.obj
+            .components
+            .get_or_insert_with(Vec::new)
+            .push(comp.into());
+        self
+    }
+
+    /// Sets the color of the bar on the left side of the container
+    pub fn accent_color(mut self, color: u32) -> Self {
+        self.obj.accent_color = Some(color);
+        self
+    }
+
+    /// Blurs the whole container as a spoiler
+    pub fn spoiler(mut self, spoiler: bool) -> Self {
+        self.obj.spoiler = Some(spoiler);
+        self
+    }
+}
+
+#[cfg(feature = "builder")]
+impl Builder<MessageComponent> for ComponentContainerBuilder {
+    type Error = ComponentLayoutBuilderError;
+
+    fn build(self) -> Result<MessageComponent, Self::Error> {
+        let children = self.obj.components();
+        if children.is_empty() {
+            return Err(ComponentLayoutBuilderError::EmptyContainer);
+        }
+        for (i, c) in children.iter().enumerate() {
+            match c.r#type {
+                ComponentType::ActionRow
+                | ComponentType::TextDisplay
+                | ComponentType::Section
+                | ComponentType::MediaGallery
+                | ComponentType::Separator
+                | ComponentType::File => {}
+                _ => return Err(ComponentLayoutBuilderError::InvalidContainerChild(i)),
+            }
+        }
+        Ok(self.obj)
+    }
+}
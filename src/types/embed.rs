@@ -6,7 +6,9 @@ use serde_with::*;
 #[cfg(feature = "builder")]
 use crate::Builder;
 #[cfg(feature = "builder")]
-use log::warn;
+use std::error;
+#[cfg(feature = "builder")]
+use std::fmt::{self, Display};
 // ======== Structures =========
 #[serde_as]
 #[skip_serializing_none]
@@ -182,18 +184,15 @@ impl Default for Embed {
 }
 #[cfg(feature = "builder")]
 impl EmbedBuilder {
-    /// Set the title of this embed
+    /// Set the title of this embed (max. 256 characters)
     pub fn title(mut self, title: impl ToString) -> Self {
         let t = title.to_string();
-        // wish this could be checked at compile time :(
-        if t.len() > 256 {
-            panic!("Embed title length is more than 256 characters.")
-        }
+
         self.obj.title = Some(t);
         self
     }
 
-    /// Add a description to the embed
+    /// Add a description to the embed (max. 4096 characters)
     pub fn description(mut self, description: impl ToString) -> Self {
         let d = description.to_string();
 
@@ -241,6 +240,7 @@ impl EmbedBuilder {
     }
 
     /// Add an [`EmbedField`] to this embed.
+    /// You can add up to 25 fields.
     pub fn add_field(mut self, field: EmbedField) -> Self {
         match self.obj.fields {
             None => {
@@ -248,11 +248,7 @@ impl EmbedBuilder {
                 self.obj.fields = nf;
             }
             Some(ref mut f) => {
-                if f.len() >= 25 {
-                    warn!("Field limit reached. Ignoring");
-                } else {
-                    f.push(field);
-                }
+                f.push(field);
             }
         }
         self
@@ -265,11 +261,131 @@ impl EmbedBuilder {
     }
 }
 
+#[cfg(feature = "builder")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// An error that occurred when building an [`Embed`]. These reflect the limits Discord imposes on embeds.
+pub enum EmbedBuilderError {
+    /// The title exceeded 256 characters
+    TitleTooLong,
+    /// The description exceeded 4096 characters
+    DescriptionTooLong,
+    /// There were over 25 fields supplied
+    TooManyFields,
+    /// The name of the field at this index exceeded 256 characters
+    FieldNameTooLong(usize),
+    /// The value of the field at this index exceeded 1024 characters
+    FieldValueTooLong(usize),
+    /// The footer text exceeded 2048 characters
+    FooterTooLong,
+    /// The author name exceeded 256 characters
+    AuthorNameTooLong,
+    /// The combined length of the title, description, fields, footer and author exceeded 6000 characters
+    TotalTooLong,
+}
+
+#[cfg(feature = "builder")]
+impl Display for EmbedBuilderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EmbedBuilderError::TitleTooLong => write!(f, "title exceeded 256 characters"),
+            EmbedBuilderError::DescriptionTooLong => {
+                write!(f, "description exceeded 4096 characters")
+            }
+            EmbedBuilderError::TooManyFields => write!(f, "over 25 fields supplied"),
+            EmbedBuilderError::FieldNameTooLong(i) => {
+                write!(f, "name of field {} exceeded 256 characters", i)
+            }
+            EmbedBuilderError::FieldValueTooLong(i) => {
+                write!(f, "value of field {} exceeded 1024 characters", i)
+            }
+            EmbedBuilderError::FooterTooLong => write!(f, "footer text exceeded 2048 characters"),
+            EmbedBuilderError::AuthorNameTooLong => {
+                write!(f, "author name exceeded 256 characters")
+            }
+            EmbedBuilderError::TotalTooLong => {
+                write!(f, "embed exceeded 6000 characters in total")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "builder")]
+impl error::Error for EmbedBuilderError {}
+
+#[cfg(feature = "builder")]
+impl Embed {
+    /// Checks this embed against the limits Discord imposes on embeds.
+    ///
+    /// This is done for you when using [`EmbedBuilder`].
+    pub fn validate(&self) -> Result<(), EmbedBuilderError> {
+        if self.title.as_deref().is_some_and(|t| len(t) > 256) {
+            return Err(EmbedBuilderError::TitleTooLong);
+        }
+        if self.description.as_deref().is_some_and(|d| len(d) > 4096) {
+            return Err(EmbedBuilderError::DescriptionTooLong);
+        }
+        if let Some(fields) = &self.fields {
+            if fields.len() > 25 {
+                return Err(EmbedBuilderError::TooManyFields);
+            }
+            for (i, f) in fields.iter().enumerate() {
+                if len(&f.name) > 256 {
+                    return Err(EmbedBuilderError::FieldNameTooLong(i));
+                }
+                if len(&f.value) > 1024 {
+                    return Err(EmbedBuilderError::FieldValueTooLong(i));
+                }
+            }
+        }
+        if self.footer.as_ref().is_some_and(|f| len(&f.text) > 2048) {
+            return Err(EmbedBuilderError::FooterTooLong);
+        }
+        if self
+            .author
+            .as_ref()
+            .and_then(|a| a.name.as_deref())
+            .is_some_and(|n| len(n) > 256)
+        {
+            return Err(EmbedBuilderError::AuthorNameTooLong);
+        }
+
+        if self.total_len() > 6000 {
+            return Err(EmbedBuilderError::TotalTooLong);
+        }
+        Ok(())
+    }
+
+    // The combined length of the title, description, fields, footer and author name.
+    // Discord limits this to 6000 characters, across all embeds of a message
+    pub(crate) fn total_len(&self) -> usize {
+        self.title.as_deref().map_or(0, len)
+            + self.description.as_deref().map_or(0, len)
+            + self
+                .fields
+                .iter()
+                .flatten()
+                .map(|f| len(&f.name) + len(&f.value))
+                .sum::<usize>()
+            + self.footer.as_ref().map_or(0, |f| len(&f.text))
+            + self
+                .author
+                .as_ref()
+                .and_then(|a| a.name.as_deref())
+                .map_or(0, len)
+    }
+}
+
+#[cfg(feature = "builder")]
+fn len(s: &str) -> usize {
+    s.chars().count()
+}
+
 #[cfg(feature = "builder")]
 impl Builder<Embed> for EmbedBuilder {
-    type Error = std::convert::Infallible;
+    type Error = EmbedBuilderError;
 
     fn build(self) -> Result<Embed, Self::Error> {
+        self.obj.validate()?;
         Ok(self.obj)
     }
 }
@@ -285,12 +401,10 @@ impl Default for EmbedFooter {
 }
 
 impl EmbedFooter {
-    /// Set the footers text
+    /// Set the footers text (max. 2048 characters)
     pub fn text(mut self, text: impl ToString) -> Self {
         let t = text.to_string();
-        if t.len() > 2048 {
-            panic!("Footer text exceeded 2048 characters")
-        }
+
         self.text = t;
         self
     }
@@ -323,23 +437,18 @@ impl Default for EmbedField {
 }
 
 impl EmbedField {
-    /// Set the field name
+    /// Set the field name (max. 256 characters)
     pub fn name(mut self, name: impl ToString) -> Self {
         let n = name.to_string();
-        if n.len() > 256 {
-            panic!("Field name is above 256 characters.")
-        }
+
         self.name = n;
         self
     }
 
-    /// Set the text of this field
+    /// Set the text of this field (max. 1024 characters)
     pub fn value(mut self, text: impl ToString) -> Self {
         let t = text.to_string();
 
-        if t.len() > 1024 {
-            panic!("Field value is above 1024 characters")
-        }
         self.value = t;
         self
     }
@@ -351,7 +460,7 @@ impl EmbedField {
 }
 
 impl EmbedAuthor {
-    /// Set the author name
+    /// Set the author name (max. 256 characters)
     pub fn name(mut self, name: impl ToString) -> Self {
         let n = name.to_string();
         self.name = Some(n);
@@ -0,0 +1,407 @@
+use std::error;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use ::chrono::{DateTime, TimeZone, Utc};
+
+use super::channel::PartialChannel;
+use super::guild::Role;
+use super::id::{ChannelId, CommandId, EmojiId, RoleId, UserId};
+use super::user::{Member, User};
+use super::Snowflake;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// An error that occurred when parsing a mention, timestamp or emoji
+pub enum FormatParseError {
+    /// The text is not in the expected `<...>` format
+    InvalidFormat,
+    /// The ID in the text is not a valid [`Snowflake`]
+    InvalidId,
+    /// The timestamp in the text is not a valid unix timestamp
+    InvalidTimestamp,
+    /// The timestamp style is unknown
+    InvalidStyle(char),
+}
+
+impl Display for FormatParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FormatParseError::InvalidFormat => write!(f, "text is not in the expected format"),
+            FormatParseError::InvalidId => write!(f, "invalid snowflake"),
+            FormatParseError::InvalidTimestamp => write!(f, "invalid unix timestamp"),
+            FormatParseError::InvalidStyle(c) => write!(f, "unknown timestamp style '{}'", c),
+        }
+    }
+}
+
+impl error::Error for FormatParseError {}
+
+fn parse_id<T: From<Snowflake>>(s: &str) -> Result<T, FormatParseError> {
+    s.parse::<Snowflake>()
+        .map(T::from)
+        .map_err(|_| FormatParseError::InvalidId)
+}
+
+fn strip_brackets(s: &str) -> Result<&str, FormatParseError> {
+    s.strip_prefix('<')
+        .and_then(|s| s.strip_suffix('>'))
+        .ok_or(FormatParseError::InvalidFormat)
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+/// A mention of a user, channel, role or command.
+///
+/// Formats as the markup Discord uses (e.g. `<@1234>`) and can be parsed back from it.
+pub enum Mention {
+    /// A user (`<@id>`)
+    User(UserId),
+    /// A channel (`<#id>`)
+    Channel(ChannelId),
+    /// A role (`<@&id>`)
+    Role(RoleId),
+    /// A slash command (`</name:id>`). The name may include subcommands separated by spaces.
+    SlashCommand {
+        /// Name of the command
+        name: String,
+        /// ID of the command
+        id: CommandId,
+    },
+    /// `@everyone`
+    Everyone,
+    /// `@here`
+    Here,
+}
+
+impl Display for Mention {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Mention::User(id) => write!(f, "<@{}>", id),
+            Mention::Channel(id) => write!(f, "<#{}>", id),
+            Mention::Role(id) => write!(f, "<@&{}>", id),
+            Mention::SlashCommand { name, id } => write!(f, "</{}:{}>", name, id),
+            Mention::Everyone => write!(f, "@everyone"),
+            Mention::Here => write!(f, "@here"),
+        }
+    }
+}
+
+impl FromStr for Mention {
+    type Err = FormatParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "@everyone" => return Ok(Mention::Everyone),
+            "@here" => return Ok(Mention::Here),
+            _ => {}
+        }
+
+        let inner = strip_brackets(s)?;
+        if let Some(id) = inner.strip_prefix("@&") {
+            Ok(Mention::Role(parse_id(id)?))
+        } else if let Some(id) = inner.strip_prefix("@!") {
+            // Legacy nickname mention
+            Ok(Mention::User(parse_id(id)?))
+        } else if let Some(id) = inner.strip_prefix('@') {
+            Ok(Mention::User(parse_id(id)?))
+        } else if let Some(id) = inner.strip_prefix('#') {
+            Ok(Mention::Channel(parse_id(id)?))
+        } else if let Some(cmd) = inner.strip_prefix('/') {
+            let (name, id) = cmd
+                .rsplit_once(':')
+                .ok_or(FormatParseError::InvalidFormat)?;
+            Ok(Mention::SlashCommand {
+                name: name.to_string(),
+                id: parse_id(id)?,
+            })
+        } else {
+            Err(FormatParseError::InvalidFormat)
+        }
+    }
+}
+
+impl Mention {
+    /// Finds all mentions in a piece of text, in the order they appear.
+    ///
+    /// Text that looks like a mention but can't be parsed is skipped.
+    pub fn parse_all(text: &str) -> Vec<Mention> {
+        let mut found = Vec::new();
+        let mut rest = text;
+
+        while let Some(start) = rest.find(['<', '@']) {
+            rest = &rest[start..];
+
+            if rest.starts_with('@') {
+                for (word, m) in [("@everyone", Mention::Everyone), ("@here", Mention::Here)] {
+                    if rest.starts_with(word) {
+                        found.push(m);
+                    }
+                }
+                rest = &rest[1..];
+                continue;
+            }
+
+            match rest.find('>') {
+                Some(end) => match rest[..=end].parse() {
+                    Ok(m) => {
+                        found.push(m);
+                        rest = &rest[end + 1..];
+                    }
+                    Err(_) => rest = &rest[1..],
+                },
+                None => break,
+            }
+        }
+        found
+    }
+}
+
+/// Anything that can be mentioned in a message
+pub trait Mentionable {
+    /// Get the [`Mention`] for this object. Use `to_string()` on it to get the markup.
+    fn mention(&self) -> Mention;
+}
+
+impl Mentionable for User {
+    fn mention(&self) -> Mention {
+        Mention::User(self.id)
+    }
+}
+
+impl Mentionable for Member {
+    fn mention(&self) -> Mention {
+        Mention::User(self.user.id)
+    }
+}
+
+impl Mentionable for Role {
+    fn mention(&self) -> Mention {
+        Mention::Role(self.id)
+    }
+}
+
+impl Mentionable for PartialChannel {
+    fn mention(&self) -> Mention {
+        Mention::Channel(self.id)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+/// How a [`Timestamp`] is displayed in the client. Rendered in the locale of the user.
+pub enum TimestampStyle {
+    /// `16:20`
+    ShortTime,
+    /// `16:20:30`
+    LongTime,
+    /// `20/04/2021`
+    ShortDate,
+    /// `20 April 2021`
+    LongDate,
+    /// `20 April 2021 16:20` (default)
+    ShortDateTime,
+    /// `Tuesday, 20 April 2021 16:20`
+    LongDateTime,
+    /// `2 months ago`
+    Relative,
+}
+
+impl TimestampStyle {
+    /// The character used in the timestamp markup
+    pub fn as_char(&self) -> char {
+        match self {
+            TimestampStyle::ShortTime => 't',
+            TimestampStyle::LongTime => 'T',
+            TimestampStyle::ShortDate => 'd',
+            TimestampStyle::LongDate => 'D',
+            TimestampStyle::ShortDateTime => 'f',
+            TimestampStyle::LongDateTime => 'F',
+            TimestampStyle::Relative => 'R',
+        }
+    }
+
+    fn from_char(c: char) -> Result<Self, FormatParseError> {
+        Ok(match c {
+            't' => TimestampStyle::ShortTime,
+            'T' => TimestampStyle::LongTime,
+            'd' => TimestampStyle::ShortDate,
+            'D' => TimestampStyle::LongDate,
+            'f' => TimestampStyle::ShortDateTime,
+            'F' => TimestampStyle::LongDateTime,
+            'R' => TimestampStyle::Relative,
+            _ => return Err(FormatParseError::InvalidStyle(c)),
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+/// A timestamp that is displayed in the local time of the user (`<t:1618953630:R>`)
+pub struct Timestamp {
+    /// The point in time
+    pub time: DateTime<Utc>,
+    /// How it should be displayed. `None` uses the client default (`ShortDateTime`)
+    pub style: Option<TimestampStyle>,
+}
+
+impl Timestamp {
+    /// Creates a new timestamp using the default style
+    pub fn new(time: DateTime<Utc>) -> Self {
+        Self { time, style: None }
+    }
+
+    /// Sets the display style
+    pub fn style(mut self, style: TimestampStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+}
+
+impl From<DateTime<Utc>> for Timestamp {
+    fn from(time: DateTime<Utc>) -> Self {
+        Timestamp::new(time)
+    }
+}
+
+impl Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.style {
+            Some(s) => write!(f, "<t:{}:{}>", self.time.timestamp(), s.as_char()),
+            None => write!(f, "<t:{}>", self.time.timestamp()),
+        }
+    }
+}
+
+impl FromStr for Timestamp {
+    type Err = FormatParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let inner = strip_brackets(s)?
+            .strip_prefix("t:")
+            .ok_or(FormatParseError::InvalidFormat)?;
+
+        let (secs, style) = match inner.split_once(':') {
+            Some((secs, style)) => {
+                let mut chars = style.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => (secs, Some(TimestampStyle::from_char(c)?)),
+                    _ => return Err(FormatParseError::InvalidFormat),
+                }
+            }
+            None => (inner, None),
+        };
+
+        let secs: i64 = secs
+            .parse()
+            .map_err(|_| FormatParseError::InvalidTimestamp)?;
+        let time = Utc
+            .timestamp_opt(secs, 0)
+            .single()
+            .ok_or(FormatParseError::InvalidTimestamp)?;
+
+        Ok(Timestamp { time, style })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+/// A custom (guild) emoji (`<:name:id>` or `<a:name:id>` if animated)
+pub struct CustomEmoji {
+    /// Name of the emoji
+    pub name: String,
+    /// ID of the emoji
+    pub id: EmojiId,
+    /// Whether the emoji is animated
+    pub animated: bool,
+}
+
+impl Display for CustomEmoji {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let prefix = if self.animated { "a" } else { "" };
+        write!(f, "<{}:{}:{}>", prefix, self.name, self.id)
+    }
+}
+
+impl FromStr for CustomEmoji {
+    type Err = FormatParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = strip_brackets(s)?.split(':');
+
+        let animated = match parts.next() {
+            Some("") => false,
+            Some("a") => true,
+            _ => return Err(FormatParseError::InvalidFormat),
+        };
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some(name), Some(id), None) if !name.is_empty() => Ok(CustomEmoji {
+                name: name.to_string(),
+                id: parse_id(id)?,
+                animated,
+            }),
+            _ => Err(FormatParseError::InvalidFormat),
+        }
+    }
+}
+
+/// Escapes markdown characters so the text is displayed as-is
+pub fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(
+            c,
+            '\\' | '*' | '_' | '~' | '`' | '|' | '>' | '#' | '-' | '[' | ']' | '(' | ')'
+        ) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Prevents `@everyone`, `@here` and user/role mentions in the text from pinging anyone.
+///
+/// Note that [`AllowedMentions`](super::interaction::AllowedMentions) is the preferred way to control pings.
+pub fn escape_mentions(text: &str) -> String {
+    // A zero-width space after the @ breaks the mention without changing how it looks
+    text.replace('@', "@\u{200B}")
+}
+
+/// Wraps the text in inline code (`` `text` ``)
+pub fn inline_code(text: &str) -> String {
+    if text.contains('`') {
+        format!("`` {} ``", text.replace("``", "`\u{200B}`"))
+    } else {
+        format!("`{}`", text)
+    }
+}
+
+/// Wraps the text in a code block, with optional syntax highlighting
+pub fn code_block(language: Option<&str>, code: &str) -> String {
+    format!(
+        "```{}\n{}\n```",
+        language.unwrap_or_default(),
+        code.replace("```", "`\u{200B}``")
+    )
+}
+
+/// Makes the text bold
+pub fn bold(text: &str) -> String {
+    format!("**{}**", text)
+}
+
+/// Makes the text italic
+pub fn italic(text: &str) -> String {
+    format!("*{}*", text)
+}
+
+/// Strikes through the text
+pub fn strikethrough(text: &str) -> String {
+    format!("~~{}~~", text)
+}
+
+/// Hides the text as a spoiler
+pub fn spoiler(text: &str) -> String {
+    format!("||{}||", text)
+}
+
+/// Creates a masked link (`[text](url)`)
+pub fn hyperlink(text: &str, url: &str) -> String {
+    format!("[{}]({})", text, url)
+}
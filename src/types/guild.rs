@@ -1,6 +1,12 @@
+use bitflags::bitflags;
 use serde::{Deserialize, Serialize};
+use serde_repr::*;
 use serde_with::*;
 
+use super::id::{
+    ApplicationId, ChannelId, EmojiId, GuildId, IntegrationId, RoleId, StickerId, UserId,
+};
+use super::user::User;
 use super::Snowflake;
 
 #[serde_as]
@@ -9,8 +15,7 @@ use super::Snowflake;
 /// A guild (also known as a 'server') in Discord
 pub struct Guild {
     /// The ID of the guild
-    #[serde_as(as = "DisplayFromStr")]
-    pub id: Snowflake,
+    pub id: GuildId,
     /// Name of this guild
     pub name: String,
 
@@ -23,48 +28,51 @@ pub struct Guild {
     pub splash: Option<String>,
     /// discovery splash hash; only present for guilds with the `DISCOVERABLE` feature
     pub discovery_splash: Option<String>,
+    /// Banner hash
+    pub banner: Option<String>,
     /// id of owner
-    #[serde_as(as = "DisplayFromStr")]
-    pub owner_id: Snowflake,
-    /// voice region id for the guild
-    pub region: String,
-    #[serde_as(as = "Option<DisplayFromStr>")]
-    #[serde(default)]
+    pub owner_id: UserId,
+    /// voice region id for the guild. Deprecated, the region is set per voice channel
+    pub region: Option<String>,
     /// Id of afk channel
-    pub afk_channel_id: Option<Snowflake>,
+    pub afk_channel_id: Option<ChannelId>,
     /// AFK timeout in seconds
     pub afk_timeout: u32,
     /// true if widget is enabled
     pub widget_enabled: Option<bool>,
     /// The channel id that the widget will generate an invite to, or null if set to no invite
-    #[serde_as(as = "Option<DisplayFromStr>")]
-    #[serde(default)]
-    pub widget_channel_id: Option<Snowflake>,
-    /// [Verfication level](https://discord.com/developers/docs/resources/guild#guild-object-verification-level) required for the guild
-    pub verfication_level: Option<u8>,
+    pub widget_channel_id: Option<ChannelId>,
+    /// [Verification level](https://discord.com/developers/docs/resources/guild#guild-object-verification-level) required for the guild
+    pub verification_level: Option<u8>,
     /// default [message notifications level](https://discord.com/developers/docs/resources/guild#guild-object-default-message-notification-level)
     pub default_message_notifications: Option<u8>,
     /// [Explicit content filter level](https://discord.com/developers/docs/resources/guild#guild-object-explicit-content-filter-level)
     pub explicit_content_filter: Option<u8>,
 
+    #[serde(default)]
     /// Roles in this guild
     pub roles: Vec<Role>,
+    #[serde(default)]
+    /// Custom emojis in this guild
+    pub emojis: Vec<Emoji>,
+    #[serde(default)]
+    /// Enabled [guild features](https://discord.com/developers/docs/resources/guild#guild-object-guild-features)
+    pub features: Vec<String>,
+    /// Custom stickers in this guild
+    pub stickers: Option<Vec<Sticker>>,
 
     /// The required [MFA level](https://discord.com/developers/docs/resources/guild#guild-object-mfa-level) in this guild
     pub mfa_level: u8,
 
-    #[serde_as(as = "Option<DisplayFromStr>")]
-    #[serde(default)]
     ///	application id of the guild creator if it is bot-created
-    pub application_id: Option<Snowflake>,
-    #[serde_as(as = "Option<DisplayFromStr>")]
-    #[serde(default)]
+    pub application_id: Option<ApplicationId>,
     /// the id of the channel where guild notices such as welcome messages and boost events are posted
-    pub system_channel_id: Option<Snowflake>,
-    /// system channel flags
-    pub system_channel_flags: Option<u8>,
+    pub system_channel_id: Option<ChannelId>,
+    #[serde(default)]
+    /// [System channel flags](https://discord.com/developers/docs/resources/guild#guild-object-system-channel-flags) combined as a bitfield
+    pub system_channel_flags: u32,
     /// the id of the channel where Community guilds can display rules and/or guidelines
-    pub rules_channel_id: Option<Snowflake>,
+    pub rules_channel_id: Option<ChannelId>,
 
     /// the vanity url code for the guild
     pub vanity_url_code: Option<String>,
@@ -74,22 +82,113 @@ pub struct Guild {
     /// premium tier (Server Boost level)
     pub premium_tier: u8,
     ///	the number of boosts this guild currently has
-    pub premium_tier_subscription_count: Option<u8>,
+    pub premium_subscription_count: Option<u32>,
 
     /// the preferred locale of a Community guild; used in server discovery and notices from Discord; defaults to "en-US"
-    pub preffered_locale: Option<String>,
+    pub preferred_locale: Option<String>,
     /// the maximum amount of users in a video channel
     pub max_video_channel_users: Option<u32>,
-    /// approximate number of members in this guild
-    pub approximate_member_count: u32,
-    /// approximate number of non-offline members in this guild
-    pub approximate_presence_count: u32,
-    /// true if this guild is [designated as NSFW](https://support.discord.com/hc/en-us/articles/1500005389362-NSFW-Server-Designation)
-    pub nsfw: bool,
+    /// the maximum number of members for the guild
+    pub max_members: Option<u32>,
+    /// approximate number of members in this guild. Only present when fetched with counts
+    pub approximate_member_count: Option<u32>,
+    /// approximate number of non-offline members in this guild. Only present when fetched with counts
+    pub approximate_presence_count: Option<u32>,
+    #[serde(default)]
+    /// [NSFW level](https://discord.com/developers/docs/resources/guild#guild-object-guild-nsfw-level) of the guild
+    pub nsfw_level: u8,
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+/// The partial guild sent along with an interaction
+pub struct PartialGuild {
+    /// The ID of the guild
+    pub id: GuildId,
+    /// The preferred locale of the guild
+    pub locale: Option<String>,
+    #[serde(default)]
+    /// Enabled [guild features](https://discord.com/developers/docs/resources/guild#guild-object-guild-features)
+    pub features: Vec<String>,
+}
+
+impl From<Guild> for PartialGuild {
+    fn from(g: Guild) -> PartialGuild {
+        PartialGuild {
+            id: g.id,
+            locale: g.preferred_locale,
+            features: g.features,
+        }
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+/// A custom emoji in a guild
+pub struct Emoji {
+    /// The ID of the emoji
+    pub id: Option<EmojiId>,
+    /// The name of the emoji. Can be `None` for deleted emojis in reactions
+    pub name: Option<String>,
+    #[serde(default)]
+    /// Roles allowed to use this emoji. Everyone can use it if empty
+    pub roles: Vec<RoleId>,
+    /// The user that created this emoji
+    pub user: Option<User>,
+    /// Whether this emoji must be wrapped in colons
+    pub require_colons: Option<bool>,
+    /// Whether this emoji is managed by an integration
+    pub managed: Option<bool>,
+    /// Whether this emoji is animated
+    pub animated: Option<bool>,
+    /// Whether this emoji can be used. May be false due to loss of Server Boosts
+    pub available: Option<bool>,
+}
+
+#[derive(Clone, Copy, Serialize_repr, Deserialize_repr, PartialEq, Eq, Debug)]
+#[repr(u8)]
+#[non_exhaustive]
+/// The file format of a [`Sticker`]
+pub enum StickerFormatType {
+    /// A PNG image
+    Png = 1,
+    /// An animated PNG image
+    Apng = 2,
+    /// A Lottie animation
+    Lottie = 3,
+    /// An animated GIF image
+    Gif = 4,
+}
+
+#[skip_serializing_none]
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+/// A custom sticker in a guild
+pub struct Sticker {
+    /// The ID of the sticker
+    pub id: StickerId,
+    /// The name of the sticker
+    pub name: String,
+    /// The description of the sticker
+    pub description: Option<String>,
+    /// Autocomplete/suggestion tags for the sticker
+    pub tags: String,
+    /// The file format of the sticker
+    pub format_type: StickerFormatType,
+    /// Whether this sticker can be used. May be false due to loss of Server Boosts
+    pub available: Option<bool>,
+    /// The ID of the guild that owns this sticker
+    pub guild_id: Option<GuildId>,
+    /// The user that uploaded this sticker
+    pub user: Option<User>,
 }
 
 impl From<Guild> for Snowflake {
     fn from(g: Guild) -> Snowflake {
+        g.id.get()
+    }
+}
+
+impl From<Guild> for GuildId {
+    fn from(g: Guild) -> GuildId {
         g.id
     }
 }
@@ -99,9 +198,8 @@ impl From<Guild> for Snowflake {
 /// A role is a way to group people in a Guild and assign certain permissions to them.
 #[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
 pub struct Role {
-    #[serde_as(as = "DisplayFromStr")]
     /// The id of the role
-    pub id: Snowflake,
+    pub id: RoleId,
 
     /// The name of the role
     pub name: String,
@@ -131,14 +229,131 @@ pub struct Role {
 /// Role tags
 #[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
 pub struct RoleTag {
-    #[serde_as(as = "Option<DisplayFromStr>")]
-    #[serde(default)]
     /// the id of the bot this role belongs to
-    pub bot_id: Option<Snowflake>,
-    #[serde_as(as = "Option<DisplayFromStr>")]
-    #[serde(default)]
+    pub bot_id: Option<UserId>,
     /// the id of the integration this role belongs to
-    pub integration_id: Option<Snowflake>,
+    pub integration_id: Option<IntegrationId>,
     /// whether this is the guild's premium subscriber role.
     pub premium_subscriber: Option<String>,
 }
+
+impl Role {
+    /// Parse the permission bit set of this role
+    pub fn permission_set(&self) -> Option<Permissions> {
+        self.permissions.parse().ok()
+    }
+}
+
+bitflags! {
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+    /// [Permissions](https://discord.com/developers/docs/topics/permissions#permissions-bitwise-permission-flags) of a member or role
+    pub struct Permissions: u64 {
+        /// Allows creation of instant invites
+        const CREATE_INSTANT_INVITE = 1 << 0;
+        /// Allows kicking members
+        const KICK_MEMBERS = 1 << 1;
+        /// Allows banning members
+        const BAN_MEMBERS = 1 << 2;
+        /// Allows all permissions and bypasses channel permission overwrites
+        const ADMINISTRATOR = 1 << 3;
+        /// Allows management and editing of channels
+        const MANAGE_CHANNELS = 1 << 4;
+        /// Allows management and editing of the guild
+        const MANAGE_GUILD = 1 << 5;
+        /// Allows for adding new reactions to messages
+        const ADD_REACTIONS = 1 << 6;
+        /// Allows for viewing of audit logs
+        const VIEW_AUDIT_LOG = 1 << 7;
+        /// Allows for using priority speaker in a voice channel
+        const PRIORITY_SPEAKER = 1 << 8;
+        /// Allows the user to go live
+        const STREAM = 1 << 9;
+        /// Allows guild members to view a channel
+        const VIEW_CHANNEL = 1 << 10;
+        /// Allows for sending messages in a channel
+        const SEND_MESSAGES = 1 << 11;
+        /// Allows for sending of `/tts` messages
+        const SEND_TTS_MESSAGES = 1 << 12;
+        /// Allows for deletion of other users messages
+        const MANAGE_MESSAGES = 1 << 13;
+        /// Links sent by users with this permission will be auto-embedded
+        const EMBED_LINKS = 1 << 14;
+        /// Allows for uploading images and files
+        const ATTACH_FILES = 1 << 15;
+        /// Allows for reading of message history
+        const READ_MESSAGE_HISTORY = 1 << 16;
+        /// Allows for using the `@everyone` and `@here` tags
+        const MENTION_EVERYONE = 1 << 17;
+        /// Allows the usage of custom emojis from other servers
+        const USE_EXTERNAL_EMOJIS = 1 << 18;
+        /// Allows for viewing guild insights
+        const VIEW_GUILD_INSIGHTS = 1 << 19;
+        /// Allows for joining of a voice channel
+        const CONNECT = 1 << 20;
+        /// Allows for speaking in a voice channel
+        const SPEAK = 1 << 21;
+        /// Allows for muting members in a voice channel
+        const MUTE_MEMBERS = 1 << 22;
+        /// Allows for deafening of members in a voice channel
+        const DEAFEN_MEMBERS = 1 << 23;
+        /// Allows for moving of members between voice channels
+        const MOVE_MEMBERS = 1 << 24;
+        /// Allows for using voice-activity-detection in a voice channel
+        const USE_VAD = 1 << 25;
+        /// Allows for modification of own nickname
+        const CHANGE_NICKNAME = 1 << 26;
+        /// Allows for modification of other users nicknames
+        const MANAGE_NICKNAMES = 1 << 27;
+        /// Allows management and editing of roles
+        const MANAGE_ROLES = 1 << 28;
+        /// Allows management and editing of webhooks
+        const MANAGE_WEBHOOKS = 1 << 29;
+        /// Allows for editing and deleting emojis, stickers, and soundboard sounds created by all users
+        const MANAGE_GUILD_EXPRESSIONS = 1 << 30;
+        /// Allows members to use application commands
+        const USE_APPLICATION_COMMANDS = 1 << 31;
+        /// Allows for requesting to speak in stage channels
+        const REQUEST_TO_SPEAK = 1 << 32;
+        /// Allows for editing and deleting scheduled events created by all users
+        const MANAGE_EVENTS = 1 << 33;
+        /// Allows for deleting and archiving threads, and viewing all private threads
+        const MANAGE_THREADS = 1 << 34;
+        /// Allows for creating public and announcement threads
+        const CREATE_PUBLIC_THREADS = 1 << 35;
+        /// Allows for creating private threads
+        const CREATE_PRIVATE_THREADS = 1 << 36;
+        /// Allows the usage of custom stickers from other servers
+        const USE_EXTERNAL_STICKERS = 1 << 37;
+        /// Allows for sending messages in threads
+        const SEND_MESSAGES_IN_THREADS = 1 << 38;
+        /// Allows for using Activities in a voice channel
+        const USE_EMBEDDED_ACTIVITIES = 1 << 39;
+        /// Allows for timing out users
+        const MODERATE_MEMBERS = 1 << 40;
+        /// Allows for viewing role subscription insights
+        const VIEW_CREATOR_MONETIZATION_ANALYTICS = 1 << 41;
+        /// Allows for using soundboard in a voice channel
+        const USE_SOUNDBOARD = 1 << 42;
+        /// Allows for creating emojis, stickers, and soundboard sounds
+        const CREATE_GUILD_EXPRESSIONS = 1 << 43;
+        /// Allows for creating scheduled events
+        const CREATE_EVENTS = 1 << 44;
+        /// Allows the usage of custom soundboard sounds from other servers
+        const USE_EXTERNAL_SOUNDS = 1 << 45;
+        /// Allows sending voice messages
+        const SEND_VOICE_MESSAGES = 1 << 46;
+        /// Allows sending polls
+        const SEND_POLLS = 1 << 49;
+        /// Allows user-installed apps to send public responses
+        const USE_EXTERNAL_APPS = 1 << 50;
+    }
+}
+
+impl std::str::FromStr for Permissions {
+    type Err = std::num::ParseIntError;
+
+    /// Parses the permission bit set Discord sends as a string. Unknown bits are kept
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Permissions::from_bits_retain)
+    }
+}
@@ -0,0 +1,185 @@
+use std::borrow::Borrow;
+use std::convert::TryFrom;
+use std::fmt::{self, Display};
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+use ::chrono::{DateTime, TimeZone, Utc};
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::Snowflake;
+
+/// The first second of 2015, which Discord uses as the epoch for snowflakes. In milliseconds since the unix epoch.
+pub const DISCORD_EPOCH: u64 = 1_420_070_400_000;
+
+/// Get the time at which the object with this snowflake was created.
+///
+/// Every snowflake contains the millisecond it was generated at in its upper 42 bits.
+pub fn snowflake_timestamp(id: Snowflake) -> DateTime<Utc> {
+    let millis = (id >> 22) + DISCORD_EPOCH;
+
+    // 42 bits of milliseconds always fit in an i64 and in chrono's range
+    Utc.timestamp_millis_opt(millis as i64).unwrap()
+}
+
+struct SnowflakeVisitor;
+
+impl<'de> Visitor<'de> for SnowflakeVisitor {
+    type Value = Snowflake;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a snowflake as string or integer")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        v.parse().map_err(E::custom)
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+        Ok(v)
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+        u64::try_from(v).map_err(E::custom)
+    }
+}
+
+macro_rules! snowflake_id {
+    ($(#[$meta:meta])* $name:ident) => {
+        $(#[$meta])*
+        ///
+        /// (De)serializes as a string, like Discord does. Any [`Snowflake`] (`u64`) can be converted into it.
+        #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+        pub struct $name(pub Snowflake);
+
+        impl $name {
+            /// Creates a new ID from a raw snowflake
+            pub const fn new(id: Snowflake) -> Self {
+                Self(id)
+            }
+
+            /// Get the raw snowflake
+            pub const fn get(self) -> Snowflake {
+                self.0
+            }
+
+            /// Get the time at which the object with this ID was created
+            pub fn created_at(self) -> DateTime<Utc> {
+                snowflake_timestamp(self.0)
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                Display::fmt(&self.0, f)
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = ParseIntError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                s.parse().map(Self)
+            }
+        }
+
+        impl From<Snowflake> for $name {
+            fn from(id: Snowflake) -> Self {
+                Self(id)
+            }
+        }
+
+        impl From<$name> for Snowflake {
+            fn from(id: $name) -> Snowflake {
+                id.0
+            }
+        }
+
+        // Allows looking up maps keyed by this ID with a raw snowflake
+        impl Borrow<Snowflake> for $name {
+            fn borrow(&self) -> &Snowflake {
+                &self.0
+            }
+        }
+
+        impl PartialEq<Snowflake> for $name {
+            fn eq(&self, other: &Snowflake) -> bool {
+                self.0 == *other
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(&self.0)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                deserializer.deserialize_any(SnowflakeVisitor).map(Self)
+            }
+        }
+    };
+}
+
+snowflake_id!(
+    /// ID of an application
+    ApplicationId
+);
+snowflake_id!(
+    /// ID of an attachment
+    AttachmentId
+);
+snowflake_id!(
+    /// ID of a channel (including threads)
+    ChannelId
+);
+snowflake_id!(
+    /// ID of an application command
+    CommandId
+);
+snowflake_id!(
+    /// ID of a custom emoji
+    EmojiId
+);
+snowflake_id!(
+    /// ID of an entitlement
+    EntitlementId
+);
+snowflake_id!(
+    /// ID of a guild
+    GuildId
+);
+snowflake_id!(
+    /// ID of an integration
+    IntegrationId
+);
+snowflake_id!(
+    /// ID of an interaction
+    InteractionId
+);
+snowflake_id!(
+    /// ID of a message
+    MessageId
+);
+snowflake_id!(
+    /// ID of a role
+    RoleId
+);
+snowflake_id!(
+    /// ID of a SKU
+    SkuId
+);
+snowflake_id!(
+    /// ID of a sticker
+    StickerId
+);
+snowflake_id!(
+    /// ID of a user
+    UserId
+);
+snowflake_id!(
+    /// ID of a webhook
+    WebhookId
+);
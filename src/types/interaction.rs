@@ -1,41 +1,60 @@
 #[cfg(feature = "extended-handler")]
 use crate::expect_successful_api_response_and_return;
 
-#[cfg(feature = "handler")]
+#[cfg(feature = "handler-core")]
 use crate::{expect_specific_api_response, expect_successful_api_response};
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
+use bitflags::bitflags;
 use serde_with::*;
 
 use serde_repr::*;
 
 use super::application::*;
+#[cfg(feature = "handler-core")]
+use super::channel::PartialChannel;
 use super::components::*;
 use super::embed::*;
+#[cfg(not(feature = "extended-handler"))]
+use super::guild::PartialGuild;
+#[cfg(all(feature = "handler-core", not(feature = "extended-handler")))]
+use super::guild::Role;
 #[cfg(feature = "extended-handler")]
 use super::guild::*;
+use super::id::*;
+use super::monetization::Entitlement;
 use super::user::*;
-#[cfg(feature = "handler")]
+#[cfg(feature = "handler-core")]
 use super::HttpError;
-use super::Snowflake;
-#[cfg(feature = "handler")]
+#[cfg(feature = "builder")]
+use crate::Builder;
+#[cfg(feature = "handler-core")]
 use ::chrono::{DateTime, Utc};
-#[cfg(feature = "handler")]
+#[cfg(feature = "handler-core")]
 use log::{debug, error};
-#[cfg(any(feature = "handler", feature = "extended-handler"))]
+#[cfg(feature = "handler-core")]
 use reqwest::{Client, StatusCode};
+#[cfg(feature = "handler-core")]
+use std::borrow::Cow;
+#[cfg(feature = "builder")]
+use std::error;
+#[cfg(feature = "builder")]
+use std::fmt::{self, Display};
 
 // ======================
 
-#[cfg(any(feature = "handler", feature = "extended-handler"))]
+#[cfg(feature = "handler-core")]
 #[derive(Clone, Debug)]
 /// A context contains relevant information and useful functions you can use when handling Interactions.
 pub struct Context {
     client: Client,
 
     /// Resolved user ID of author
-    pub author_id: Option<Snowflake>,
+    pub author_id: Option<UserId>,
+
+    // Applied to followups and edits that don't set their own allowed mentions
+    pub(crate) default_allowed_mentions: Option<AllowedMentions>,
 
     /// The [`Interaction`] sent by Discord.
     pub interaction: Interaction,
@@ -46,27 +65,21 @@ pub struct Context {
 /// The base Interaction structure. When Interactions are received, this structure is wrapped inside a [`Context`]
 /// and can be used to get information about the Interaction.
 pub struct Interaction {
-    #[serde_as(as = "Option<DisplayFromStr>")]
-    #[serde(default)]
     /// The application id of your applicaton
-    pub application_id: Option<Snowflake>,
+    pub application_id: Option<ApplicationId>,
 
-    #[serde_as(as = "Option<DisplayFromStr>")]
-    #[serde(default)]
     /// Unique id identifying the interaction
-    pub id: Option<Snowflake>,
+    pub id: Option<InteractionId>,
     /// The type of interaction
     pub r#type: InteractionType,
     /// Interaction data, if applicable
     pub data: Option<ApplicationCommandInteractionData>,
-    #[serde_as(as = "Option<DisplayFromStr>")]
-    #[serde(default)]
     /// The ID of the guild where the Interaction took place (None if in DM)
-    pub guild_id: Option<Snowflake>,
-    #[serde_as(as = "Option<DisplayFromStr>")]
-    #[serde(default)]
+    pub guild_id: Option<GuildId>,
+    /// The guild where the Interaction took place (None if in DM)
+    pub guild: Option<PartialGuild>,
     /// The channel ID where the Interaction took place
-    pub channel_id: Option<Snowflake>,
+    pub channel_id: Option<ChannelId>,
     /// The [`Member`] who invoked the command (None if in DM, use [`User`] instead)
     pub member: Option<Member>,
     /// The [`User`] who invoked the command (None if in guild, use [`Member`] instead)
@@ -75,8 +88,42 @@ pub struct Interaction {
     pub token: Option<String>,
     /// The locale the client is set to.
     pub locale: Option<String>,
+    /// The preferred locale of the guild, if invoked in a guild
+    pub guild_locale: Option<String>,
     /// Read-only. Always `1`
     pub version: Option<i8>,
+    /// Entitlements of the invoking user or guild, representing access to premium SKUs
+    pub entitlements: Option<Vec<Entitlement>>,
+    /// The message the component was attached to. Only present for component interactions
+    pub message: Option<PartialMessage>,
+}
+
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+/// The message a component interaction was triggered on
+pub struct PartialMessage {
+    /// The ID of the message
+    pub id: MessageId,
+    /// The channel the message was sent in
+    pub channel_id: ChannelId,
+    /// The contents of the message
+    pub content: Option<String>,
+    /// Set if the message is a response to an interaction
+    pub interaction_metadata: Option<MessageInteractionMetadata>,
+}
+
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+/// Information about the interaction a message is a response to
+pub struct MessageInteractionMetadata {
+    /// The ID of the interaction
+    pub id: InteractionId,
+    /// The type of interaction
+    pub r#type: InteractionType,
+    /// The user who triggered the interaction
+    pub user: Option<User>,
 }
 
 #[derive(Clone, Serialize_repr, Deserialize_repr, PartialEq, Debug)]
@@ -91,6 +138,9 @@ pub enum InteractionType {
 
     /// A message component
     MessageComponent = 3,
+
+    /// A submitted modal
+    ModalSubmit = 5,
 }
 
 #[serde_as]
@@ -107,15 +157,19 @@ pub struct InteractionResponse {
     pub data: Option<InteractionApplicationCommandCallbackData>,
 }
 
-#[cfg(feature = "handler")]
+#[cfg(feature = "handler-core")]
 #[derive(Clone, Debug)]
 /// Builder for making a [`InteractionResponse`]
-
+///
+/// Use the [`Builder::build`] function to get a validated [`InteractionResponse`].
 pub struct InteractionResponseBuilder {
     #[doc(hidden)]
     pub r#type: InteractionResponseType,
     #[doc(hidden)]
     pub data: Option<InteractionApplicationCommandCallbackData>,
+
+    // The type of interaction that is being responded to, if known.
+    interaction_type: Option<InteractionType>,
 }
 
 impl InteractionResponse {
@@ -129,9 +183,42 @@ impl InteractionResponse {
             data,
         }
     }
+
+    // Sets the allowed mentions of a message response, if the handler didn't set them itself
+    #[cfg(feature = "handler-core")]
+    pub(crate) fn apply_default_allowed_mentions(&mut self, default: Option<&AllowedMentions>) {
+        let sends_message = matches!(
+            self.r#type,
+            InteractionResponseType::ChannelMessageWithSource
+                | InteractionResponseType::UpdateMessage
+        );
+
+        if let (true, Some(default), Some(data)) = (sends_message, default, self.data.as_mut()) {
+            if data.allowed_mentions.is_none() {
+                data.allowed_mentions = Some(default.clone());
+            }
+        }
+    }
+
+    // Turns the response into a follow-up message. Unlike edits, follow-ups can be ephemeral
+    #[cfg(feature = "handler-core")]
+    pub(crate) fn into_followup(self) -> WebhookMessage {
+        let ephemeral = self
+            .data
+            .as_ref()
+            .and_then(|d| d.flags)
+            .is_some_and(|f| f.contains(MessageFlags::EPHEMERAL));
+
+        let message = WebhookMessage::from(self);
+        if ephemeral {
+            message.is_ephemeral(true)
+        } else {
+            message
+        }
+    }
 }
 
-#[cfg(feature = "handler")]
+#[cfg(feature = "handler-core")]
 impl Default for InteractionResponseBuilder {
     /// This will default to responding with the `InteractionResponseType::CHANNEL_MESSAGE_WITH_SOURCE` response type and no data.
     /// Adding data yourself is expected.
@@ -139,11 +226,12 @@ impl Default for InteractionResponseBuilder {
         Self {
             r#type: InteractionResponseType::ChannelMessageWithSource,
             data: None,
+            interaction_type: None,
         }
     }
 }
 
-#[cfg(feature = "handler")]
+#[cfg(feature = "handler-core")]
 impl InteractionResponseBuilder {
     fn ret(self) -> InteractionResponse {
         InteractionResponse {
@@ -156,14 +244,21 @@ impl InteractionResponseBuilder {
     pub fn pong(mut self) -> InteractionResponse {
         self.r#type = InteractionResponseType::Pong;
         self.data = None;
-        self.finish()
+        self.ret()
+    }
+
+    /// Return a premium required response, prompting the user to upgrade. Requires monetization to be enabled.
+    pub fn premium_required(mut self) -> InteractionResponse {
+        self.r#type = InteractionResponseType::PremiumRequired;
+        self.data = None;
+        self.ret()
     }
 
     /// Return without any data. Use with caution
     pub fn none(mut self) -> InteractionResponse {
         self.r#type = InteractionResponseType::None;
         self.data = None;
-        self.finish()
+        self.ret()
     }
 
     /// Sets the [`InteractionResponseType`]
@@ -212,35 +307,58 @@ impl InteractionResponseBuilder {
         self.content(c)
     }
 
+    fn set_flag(mut self, flag: MessageFlags, enable: bool) -> Self {
+        let d = self
+            .data
+            .get_or_insert_with(InteractionApplicationCommandCallbackData::new);
+
+        let mut flags = d.flags.unwrap_or_default();
+        flags.set(flag, enable);
+        d.flags = Some(flags);
+
+        self
+    }
+
+    /// Sets the [`MessageFlags`] of this response, overwriting previously set flags.
+    ///
+    /// Only `EPHEMERAL`, `SUPPRESS_EMBEDS`, `SUPPRESS_NOTIFICATIONS` and `IS_COMPONENTS_V2` can be set on a response.
+    pub fn flags(mut self, flags: MessageFlags) -> Self {
+        self.data
+            .get_or_insert_with(InteractionApplicationCommandCallbackData::new)
+            .flags = Some(flags);
+        self
+    }
+
     /// Sets the 'ephermeral' message flag. This will cause the message to show for its recipient only.
-    pub fn is_ephemeral(mut self, e: bool) -> Self {
-        match self.data.as_mut() {
-            None => {
-                let mut d = InteractionApplicationCommandCallbackData::new();
-                if e {
-                    d.flags = Some(1 << 6);
-                }
-                self.data = Some(d);
-            }
-            Some(d) => {
-                if let Some(mut flag) = d.flags {
-                    if e {
-                        flag |= 1 << 6;
-                    } else {
-                        flag |= 0 << 6;
-                    }
-                    d.flags = Some(flag);
-                } else if e {
-                    d.flags = Some(1 << 6);
-                } else {
-                    d.flags = Some(0);
-                }
-            }
-        }
+    pub fn is_ephemeral(self, e: bool) -> Self {
+        self.set_flag(MessageFlags::EPHEMERAL, e)
+    }
 
+    /// Sets who can be pinged by this response.
+    ///
+    /// This overrides the default set with [`InteractionHandler::set_default_allowed_mentions`](crate::handler::InteractionHandler::set_default_allowed_mentions).
+    pub fn allowed_mentions(mut self, am: AllowedMentions) -> Self {
+        self.data
+            .get_or_insert_with(InteractionApplicationCommandCallbackData::new)
+            .allowed_mentions = Some(am);
         self
     }
 
+    /// Sets the `SUPPRESS_EMBEDS` message flag. This will hide link embeds in the message.
+    pub fn suppress_embeds(self, s: bool) -> Self {
+        self.set_flag(MessageFlags::SUPPRESS_EMBEDS, s)
+    }
+
+    /// Sets the `SUPPRESS_NOTIFICATIONS` message flag. This will not trigger push and desktop notifications.
+    pub fn suppress_notifications(self, s: bool) -> Self {
+        self.set_flag(MessageFlags::SUPPRESS_NOTIFICATIONS, s)
+    }
+
+    /// Sets the `IS_COMPONENTS_V2` message flag. This allows the use of layout components, but disables `content` and `embeds`.
+    pub fn components_v2(self, c: bool) -> Self {
+        self.set_flag(MessageFlags::IS_COMPONENTS_V2, c)
+    }
+
     /// Add an [`Embed`] to the response.
     /// You can add up to 10 embeds.
     pub fn add_embed(mut self, e: &Embed) -> Self {
@@ -255,41 +373,33 @@ impl InteractionResponseBuilder {
                     d.embeds = Some(vec![e.clone()]);
                 } else {
                     let v = d.embeds.as_mut().unwrap();
-                    // Check if this will exceed the embed limit
-                    if v.len() <= 9 {
-                        v.push(e.clone());
-                    } else {
-                        // Log an error for now.
-                        error!("Tried to add embed while embed limit (max. 10 embeds) was already reached. Ignoring")
-                    }
+                    v.push(e.clone());
                 }
             }
         }
         self
     }
 
-    /// Add components to response
-    pub fn add_component_row(mut self, comp: impl Into<MessageComponent>) -> Self {
-        let component = comp.into();
-        match self.data.as_mut() {
-            None => {
-                let mut d = InteractionApplicationCommandCallbackData::new();
-                d.components = Some(vec![component]);
-                self.data = Some(d);
-            }
-            Some(d) => {
-                if d.components.is_none() {
-                    d.components = Some(vec![component]);
-                } else {
-                    let comp = d.components.as_mut().unwrap();
+    /// Add components to response.
+    /// You can add up to 5 action rows.
+    pub fn add_component_row(self, comp: impl Into<MessageComponent>) -> Self {
+        self.add_component(comp)
+    }
 
-                    comp.push(component);
-                }
-            }
-        }
+    /// Add a top-level component to the response.
+    ///
+    /// Without the `IS_COMPONENTS_V2` flag (see [`components_v2`](Self::components_v2)) these must be action rows.
+    /// With it, you can also add layout components such as sections, text displays and containers.
+    pub fn add_component(mut self, comp: impl Into<MessageComponent>) -> Self {
+        self.data
+            .get_or_insert_with(InteractionApplicationCommandCallbackData::new)
+            .components
+            .get_or_insert_with(Vec::new)
+            .push(comp.into());
         self
     }
 
+    #[deprecated(since = "0.4.0", note = "Use the `build()` function instead")]
     /// Returns an `InteractionResponse`, consuming itself.
     /// You can't use the builder anymore after you called this function.
     pub fn finish(self) -> InteractionResponse {
@@ -297,6 +407,229 @@ impl InteractionResponseBuilder {
     }
 }
 
+#[cfg(feature = "handler-core")]
+#[derive(Debug, Clone, PartialEq)]
+/// An error that occurred when building an [`InteractionResponse`]
+pub enum ResponseError {
+    /// The message content exceeded 2000 characters
+    ContentTooLong,
+    /// There were over 10 embeds supplied
+    TooManyEmbeds,
+    /// One of the embeds is invalid
+    InvalidEmbed(EmbedBuilderError),
+    /// The embeds exceeded 6000 characters in total
+    EmbedsTooLong,
+    /// There were over 5 action rows supplied
+    TooManyActionRows,
+    /// A top-level component at this index is not an action row
+    NotAnActionRow(usize),
+    /// The action row at this index contains more than 5 buttons
+    TooManyButtons(usize),
+    /// The action row at this index contains more than one select menu, or a select menu together with buttons
+    InvalidSelectMenuRow(usize),
+    /// The response has message flags set that cannot be used in a response
+    UnsupportedFlags(MessageFlags),
+    /// A layout component at this index was used without the `IS_COMPONENTS_V2` flag
+    ComponentsV2NotEnabled(usize),
+    /// The top-level component at this index cannot be placed at the top level
+    InvalidTopLevelComponent(usize),
+    /// Content or embeds were set while the `IS_COMPONENTS_V2` flag is set
+    ContentWithComponentsV2,
+    /// There were over 40 components (including nested ones) supplied
+    TooManyComponents,
+    /// The response type cannot be used to respond to this type of interaction
+    InvalidResponseType {
+        /// The type of response
+        response: InteractionResponseType,
+        /// The type of interaction that was responded to
+        interaction: InteractionType,
+    },
+}
+
+#[cfg(feature = "handler-core")]
+impl Display for ResponseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ResponseError::ContentTooLong => write!(f, "content exceeded 2000 characters"),
+            ResponseError::TooManyEmbeds => write!(f, "over 10 embeds supplied"),
+            ResponseError::InvalidEmbed(e) => write!(f, "invalid embed: {}", e),
+            ResponseError::EmbedsTooLong => write!(f, "embeds exceeded 6000 characters in total"),
+            ResponseError::TooManyActionRows => write!(f, "over 5 action rows supplied"),
+            ResponseError::NotAnActionRow(i) => {
+                write!(f, "top-level component {} is not an action row", i)
+            }
+            ResponseError::TooManyButtons(i) => {
+                write!(f, "action row {} contains over 5 buttons", i)
+            }
+            ResponseError::InvalidSelectMenuRow(i) => write!(
+                f,
+                "action row {} contains a select menu alongside other components",
+                i
+            ),
+            ResponseError::UnsupportedFlags(fl) => {
+                write!(f, "flags {:?} cannot be set on a response", fl)
+            }
+            ResponseError::ComponentsV2NotEnabled(i) => write!(
+                f,
+                "top-level component {} requires the IS_COMPONENTS_V2 flag",
+                i
+            ),
+            ResponseError::InvalidTopLevelComponent(i) => {
+                write!(
+                    f,
+                    "top-level component {} cannot be placed at the top level",
+                    i
+                )
+            }
+            ResponseError::ContentWithComponentsV2 => write!(
+                f,
+                "content and embeds cannot be used with the IS_COMPONENTS_V2 flag"
+            ),
+            ResponseError::TooManyComponents => write!(f, "over 40 components supplied"),
+            ResponseError::InvalidResponseType {
+                response,
+                interaction,
+            } => write!(
+                f,
+                "cannot respond with {:?} to a {:?} interaction",
+                response, interaction
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "handler-core")]
+impl error::Error for ResponseError {}
+
+#[cfg(feature = "handler-core")]
+impl InteractionResponseType {
+    /// Whether this response type can be used to respond to the given [`InteractionType`]
+    pub fn is_allowed_for(&self, interaction: &InteractionType) -> bool {
+        use InteractionResponseType::*;
+
+        match interaction {
+            InteractionType::Ping => matches!(self, Pong),
+            InteractionType::ApplicationCommand => matches!(
+                self,
+                None | ChannelMessageWithSource
+                    | DefferedChannelMessageWithSource
+                    | PremiumRequired
+            ),
+            InteractionType::MessageComponent => !matches!(self, Pong),
+            // Modals that were opened from a component can update the message of that component
+            InteractionType::ModalSubmit => !matches!(self, Pong | PremiumRequired),
+        }
+    }
+}
+
+#[cfg(feature = "handler-core")]
+impl Builder<InteractionResponse> for InteractionResponseBuilder {
+    type Error = ResponseError;
+
+    fn build(self) -> Result<InteractionResponse, Self::Error> {
+        if let Some(it) = &self.interaction_type {
+            if !self.r#type.is_allowed_for(it) {
+                return Err(ResponseError::InvalidResponseType {
+                    response: self.r#type,
+                    interaction: it.clone(),
+                });
+            }
+        }
+
+        if let Some(d) = &self.data {
+            if let Some(fl) = d.flags {
+                let unsupported = fl.difference(MessageFlags::RESPONSE_FLAGS);
+                if !unsupported.is_empty() {
+                    return Err(ResponseError::UnsupportedFlags(unsupported));
+                }
+            }
+
+            check_content(d.content.as_deref(), d.embeds.as_deref())?;
+
+            let v2 = d
+                .flags
+                .is_some_and(|f| f.contains(MessageFlags::IS_COMPONENTS_V2));
+
+            if v2 && (d.content.is_some() || d.embeds.is_some()) {
+                return Err(ResponseError::ContentWithComponentsV2);
+            }
+
+            if let Some(components) = &d.components {
+                if v2 {
+                    if components.iter().map(|c| c.count()).sum::<usize>() > 40 {
+                        return Err(ResponseError::TooManyComponents);
+                    }
+                } else if components.len() > 5 {
+                    return Err(ResponseError::TooManyActionRows);
+                }
+
+                for (i, comp) in components.iter().enumerate() {
+                    match comp.component_type() {
+                        ComponentType::ActionRow => check_action_row(i, comp)?,
+                        t if t.is_v2() && !v2 => {
+                            return Err(ResponseError::ComponentsV2NotEnabled(i))
+                        }
+                        ComponentType::Container => {
+                            for row in comp.components() {
+                                if row.component_type() == &ComponentType::ActionRow {
+                                    check_action_row(i, row)?;
+                                }
+                            }
+                        }
+                        ComponentType::TextDisplay
+                        | ComponentType::Section
+                        | ComponentType::MediaGallery
+                        | ComponentType::File
+                        | ComponentType::Separator => {}
+                        _ if v2 => return Err(ResponseError::InvalidTopLevelComponent(i)),
+                        _ => return Err(ResponseError::NotAnActionRow(i)),
+                    }
+                }
+            }
+        }
+
+        Ok(self.ret())
+    }
+}
+
+// The limits on the content and embeds of a message, shared by responses and webhook messages
+#[cfg(feature = "handler-core")]
+fn check_content(content: Option<&str>, embeds: Option<&[Embed]>) -> Result<(), ResponseError> {
+    if content.is_some_and(|c| c.chars().count() > 2000) {
+        return Err(ResponseError::ContentTooLong);
+    }
+
+    if let Some(embeds) = embeds {
+        if embeds.len() > 10 {
+            return Err(ResponseError::TooManyEmbeds);
+        }
+        for e in embeds {
+            e.validate().map_err(ResponseError::InvalidEmbed)?;
+        }
+        if embeds.iter().map(Embed::total_len).sum::<usize>() > 6000 {
+            return Err(ResponseError::EmbedsTooLong);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "handler-core")]
+fn check_action_row(i: usize, row: &MessageComponent) -> Result<(), ResponseError> {
+    let children = row.components();
+    let selects = children
+        .iter()
+        .filter(|c| c.component_type() != &ComponentType::Button)
+        .count();
+
+    if selects > 0 && children.len() > 1 {
+        return Err(ResponseError::InvalidSelectMenuRow(i));
+    }
+    if children.len() > 5 {
+        return Err(ResponseError::TooManyButtons(i));
+    }
+    Ok(())
+}
+
 #[derive(Clone, Serialize_repr, Deserialize_repr, Debug, PartialEq)]
 #[repr(u8)]
 
@@ -318,6 +651,11 @@ pub enum InteractionResponseType {
 
     /// For components, edit the message the component was attached to
     UpdateMessage = 7,
+
+    /// Respond to an [`Interaction`] with an upgrade button, only available for apps with monetization enabled
+    ///
+    /// Discord has deprecated this response type in favor of buttons with the `Premium` style.
+    PremiumRequired = 10,
 }
 
 #[serde_as]
@@ -329,10 +667,70 @@ pub struct InteractionApplicationCommandCallbackData {
     content: Option<String>,
     embeds: Option<Vec<Embed>>,
     allowed_mentions: Option<AllowedMentions>,
-    flags: Option<u8>,
+    flags: Option<MessageFlags>,
     components: Option<Vec<MessageComponent>>,
 }
 
+bitflags! {
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+    /// [Flags](https://discord.com/developers/docs/resources/message#message-object-message-flags) that can be set on a message
+    pub struct MessageFlags: u64 {
+        /// This message has been published to subscribed channels
+        const CROSSPOSTED = 1 << 0;
+        /// This message originated from a message in another channel
+        const IS_CROSSPOST = 1 << 1;
+        /// Do not include any embeds when serializing this message
+        const SUPPRESS_EMBEDS = 1 << 2;
+        /// The source message for this crosspost has been deleted
+        const SOURCE_MESSAGE_DELETED = 1 << 3;
+        /// This message came from the urgent message system
+        const URGENT = 1 << 4;
+        /// This message has an associated thread, with the same id as the message
+        const HAS_THREAD = 1 << 5;
+        /// This message is only visible to the user who invoked the Interaction
+        const EPHEMERAL = 1 << 6;
+        /// This message is an Interaction Response and the bot is "thinking"
+        const LOADING = 1 << 7;
+        /// This message failed to mention some roles and add their members to the thread
+        const FAILED_TO_MENTION_SOME_ROLES_IN_THREAD = 1 << 8;
+        /// This message will not trigger push and desktop notifications
+        const SUPPRESS_NOTIFICATIONS = 1 << 12;
+        /// This message is a voice message
+        const IS_VOICE_MESSAGE = 1 << 13;
+        /// This message has a snapshot (via Message Forwarding)
+        const HAS_SNAPSHOT = 1 << 14;
+        /// This message uses layout components instead of `content` and `embeds`
+        const IS_COMPONENTS_V2 = 1 << 15;
+    }
+}
+
+impl MessageFlags {
+    /// Flags that can be set when responding to an interaction
+    pub const RESPONSE_FLAGS: MessageFlags = MessageFlags::EPHEMERAL
+        .union(MessageFlags::SUPPRESS_EMBEDS)
+        .union(MessageFlags::SUPPRESS_NOTIFICATIONS)
+        .union(MessageFlags::IS_COMPONENTS_V2);
+
+    /// Flags that can be set when editing a message
+    pub const EDIT_FLAGS: MessageFlags =
+        MessageFlags::SUPPRESS_EMBEDS.union(MessageFlags::IS_COMPONENTS_V2);
+}
+
+impl Serialize for MessageFlags {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(self.bits())
+    }
+}
+
+impl<'de> Deserialize<'de> for MessageFlags {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // Keep unknown bits, Discord adds new flags every now and then
+        Ok(MessageFlags::from_bits_retain(u64::deserialize(
+            deserializer,
+        )?))
+    }
+}
+
 impl InteractionApplicationCommandCallbackData {
     /// Creates a new [`InteractionApplicationCommandCallbackData`]
     pub fn new() -> Self {
@@ -340,7 +738,8 @@ impl InteractionApplicationCommandCallbackData {
     }
 }
 
-#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
 /// Representing the allowed mention type
 pub enum AllowedMentionTypes {
     /// Role mentions
@@ -352,13 +751,146 @@ pub enum AllowedMentionTypes {
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Default)]
-/// Representing the AllowedMentions data model
+/// Representing the AllowedMentions data model. Controls who can be pinged by a message.
+///
+/// The default value does not allow any pings. Use [`AllowedMentionsBuilder`] to build one.
 pub struct AllowedMentions {
+    /// Mention types that are allowed to ping
     pub parse: Vec<AllowedMentionTypes>,
-    pub roles: Vec<Snowflake>,
-    pub users: Vec<Snowflake>,
+    /// Roles that are allowed to be pinged (max 100)
+    pub roles: Vec<RoleId>,
+    /// Users that are allowed to be pinged (max 100)
+    pub users: Vec<UserId>,
+    /// For replies, whether to ping the author of the message being replied to
     pub replied_user: bool,
 }
+
+impl AllowedMentions {
+    /// Allows no mentions to ping
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    /// Allows every user, role and @everyone mention to ping
+    pub fn all() -> Self {
+        Self {
+            parse: vec![
+                AllowedMentionTypes::Users,
+                AllowedMentionTypes::Roles,
+                AllowedMentionTypes::Everyone,
+            ],
+            replied_user: true,
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(feature = "builder")]
+#[derive(Clone, Debug, Default)]
+/// Builder for [`AllowedMentions`]. Nothing is allowed to ping unless enabled.
+pub struct AllowedMentionsBuilder {
+    obj: AllowedMentions,
+}
+
+#[cfg(feature = "builder")]
+impl AllowedMentionsBuilder {
+    fn set_parse(mut self, t: AllowedMentionTypes, enable: bool) -> Self {
+        self.obj.parse.retain(|p| p != &t);
+        if enable {
+            self.obj.parse.push(t);
+        }
+        self
+    }
+
+    /// Allow @everyone and @here to ping
+    pub fn everyone(self, enable: bool) -> Self {
+        self.set_parse(AllowedMentionTypes::Everyone, enable)
+    }
+
+    /// Allow all mentioned users to ping. Cannot be combined with [`add_user`](Self::add_user)
+    pub fn all_users(self, enable: bool) -> Self {
+        self.set_parse(AllowedMentionTypes::Users, enable)
+    }
+
+    /// Allow all mentioned roles to ping. Cannot be combined with [`add_role`](Self::add_role)
+    pub fn all_roles(self, enable: bool) -> Self {
+        self.set_parse(AllowedMentionTypes::Roles, enable)
+    }
+
+    /// Allow this user to be pinged
+    pub fn add_user(mut self, id: impl Into<UserId>) -> Self {
+        self.obj.users.push(id.into());
+        self
+    }
+
+    /// Allow this role to be pinged
+    pub fn add_role(mut self, id: impl Into<RoleId>) -> Self {
+        self.obj.roles.push(id.into());
+        self
+    }
+
+    /// For replies, whether to ping the author of the message being replied to
+    pub fn replied_user(mut self, enable: bool) -> Self {
+        self.obj.replied_user = enable;
+        self
+    }
+}
+
+#[cfg(feature = "builder")]
+#[derive(Debug, Clone, PartialEq)]
+/// An error that occurred when building [`AllowedMentions`]
+pub enum AllowedMentionsBuilderError {
+    /// All users were allowed, while also listing specific users
+    UsersConflict,
+    /// All roles were allowed, while also listing specific roles
+    RolesConflict,
+    /// There were over 100 users supplied
+    TooManyUsers,
+    /// There were over 100 roles supplied
+    TooManyRoles,
+}
+
+#[cfg(feature = "builder")]
+impl Display for AllowedMentionsBuilderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AllowedMentionsBuilderError::UsersConflict => {
+                write!(f, "cannot allow all users and specific users at once")
+            }
+            AllowedMentionsBuilderError::RolesConflict => {
+                write!(f, "cannot allow all roles and specific roles at once")
+            }
+            AllowedMentionsBuilderError::TooManyUsers => write!(f, "over 100 users supplied"),
+            AllowedMentionsBuilderError::TooManyRoles => write!(f, "over 100 roles supplied"),
+        }
+    }
+}
+
+#[cfg(feature = "builder")]
+impl error::Error for AllowedMentionsBuilderError {}
+
+#[cfg(feature = "builder")]
+impl Builder<AllowedMentions> for AllowedMentionsBuilder {
+    type Error = AllowedMentionsBuilderError;
+
+    fn build(self) -> Result<AllowedMentions, Self::Error> {
+        let parse = &self.obj.parse;
+
+        if parse.contains(&AllowedMentionTypes::Users) && !self.obj.users.is_empty() {
+            return Err(AllowedMentionsBuilderError::UsersConflict);
+        }
+        if parse.contains(&AllowedMentionTypes::Roles) && !self.obj.roles.is_empty() {
+            return Err(AllowedMentionsBuilderError::RolesConflict);
+        }
+        if self.obj.users.len() > 100 {
+            return Err(AllowedMentionsBuilderError::TooManyUsers);
+        }
+        if self.obj.roles.len() > 100 {
+            return Err(AllowedMentionsBuilderError::TooManyRoles);
+        }
+        Ok(self.obj)
+    }
+}
 #[serde_as]
 #[skip_serializing_none]
 #[derive(Clone, Default, Serialize, Deserialize, Debug)]
@@ -375,8 +907,10 @@ pub struct WebhookMessage {
     /// Used for files.
     pub payload_json: Option<String>,
     pub allowed_mentions: Option<AllowedMentions>,
+    /// Message flags. Only `EPHEMERAL` (followups only), `SUPPRESS_EMBEDS`, `SUPPRESS_NOTIFICATIONS` and `IS_COMPONENTS_V2` can be set
+    pub flags: Option<MessageFlags>,
 }
-#[cfg(feature = "handler")]
+#[cfg(feature = "handler-core")]
 impl WebhookMessage {
     /// Add text to this WebhookMessage
     pub fn content(mut self, content: impl ToString) -> Self {
@@ -384,24 +918,88 @@ impl WebhookMessage {
         self
     }
 
-    /// Add an embed to this WebhookMessage
-    pub fn add_embed(mut self, embed: Embed) -> Self {
-        match self.embeds.as_mut() {
-            None => {
-                self.embeds = Some(vec![embed]);
-            }
-            Some(e) => {
-                // Check if this will exceed the embed limit
-                if e.len() <= 9 {
-                    e.push(embed);
-                } else {
-                    // Log an error for now.
-                    error!("Tried to add embed while embed limit (max. 10 embeds) was already reached. Ignoring")
-                }
-            }
+    /// Sets who can be pinged by this message.
+    ///
+    /// This overrides the default set with [`InteractionHandler::set_default_allowed_mentions`](crate::handler::InteractionHandler::set_default_allowed_mentions).
+    pub fn allowed_mentions(mut self, am: AllowedMentions) -> Self {
+        self.allowed_mentions = Some(am);
+        self
+    }
+
+    // Rejects a message Discord would reject, without sending it
+    fn validate_for_sending(&self) -> Result<(), HttpError> {
+        self.validate().map_err(|e| {
+            debug!("Invalid webhook message: {}", e);
+            HttpError::new(400, e.to_string())
+        })
+    }
+
+    fn or_default_mentions<'a>(&'a self, default: Option<&AllowedMentions>) -> Cow<'a, Self> {
+        match default {
+            Some(am) if self.allowed_mentions.is_none() => Cow::Owned(WebhookMessage {
+                allowed_mentions: Some(am.clone()),
+                ..self.clone()
+            }),
+            _ => Cow::Borrowed(self),
         }
+    }
+
+    fn set_flag(mut self, flag: MessageFlags, enable: bool) -> Self {
+        self.flags
+            .get_or_insert_with(MessageFlags::empty)
+            .set(flag, enable);
         self
     }
+
+    /// Sets the [`MessageFlags`] of this message, overwriting previously set flags.
+    pub fn flags(mut self, flags: MessageFlags) -> Self {
+        self.flags = Some(flags);
+        self
+    }
+
+    /// Sets the 'ephermeral' message flag. Only applies to followup messages.
+    pub fn is_ephemeral(self, e: bool) -> Self {
+        self.set_flag(MessageFlags::EPHEMERAL, e)
+    }
+
+    /// Sets the `SUPPRESS_EMBEDS` message flag. This will hide link embeds in the message.
+    pub fn suppress_embeds(self, s: bool) -> Self {
+        self.set_flag(MessageFlags::SUPPRESS_EMBEDS, s)
+    }
+
+    /// Sets the `SUPPRESS_NOTIFICATIONS` message flag. This will not trigger push and desktop notifications.
+    pub fn suppress_notifications(self, s: bool) -> Self {
+        self.set_flag(MessageFlags::SUPPRESS_NOTIFICATIONS, s)
+    }
+
+    /// Sets the `IS_COMPONENTS_V2` message flag. This allows the use of layout components, but disables `content` and `embeds`.
+    pub fn components_v2(self, c: bool) -> Self {
+        self.set_flag(MessageFlags::IS_COMPONENTS_V2, c)
+    }
+
+    /// Add a top-level component to this WebhookMessage.
+    ///
+    /// Layout components such as sections and containers require the `IS_COMPONENTS_V2` flag (see [`components_v2`](Self::components_v2)).
+    pub fn add_component(mut self, comp: impl Into<MessageComponent>) -> Self {
+        self.components
+            .get_or_insert_with(Vec::new)
+            .push(comp.into());
+        self
+    }
+
+    /// Add an embed to this WebhookMessage.
+    /// You can add up to 10 embeds, see [`validate`](Self::validate).
+    pub fn add_embed(mut self, embed: Embed) -> Self {
+        self.embeds.get_or_insert_with(Vec::new).push(embed);
+        self
+    }
+
+    /// Checks the content and embeds of this message against the limits Discord imposes, like [`InteractionResponseBuilder::build`] does.
+    ///
+    /// This is done for you when sending the message.
+    pub fn validate(&self) -> Result<(), ResponseError> {
+        check_content(self.content.as_deref(), self.embeds.as_deref())
+    }
 }
 
 impl From<InteractionResponse> for WebhookMessage {
@@ -412,6 +1010,12 @@ impl From<InteractionResponse> for WebhookMessage {
             content: data.content,
             embeds: data.embeds,
             components: data.components,
+            // Flags like EPHEMERAL only apply to new messages, and are rejected on edits
+            flags: data
+                .flags
+                .map(|f| f & MessageFlags::EDIT_FLAGS)
+                .filter(|f| !f.is_empty()),
+            allowed_mentions: data.allowed_mentions,
             ..Default::default()
         }
     }
@@ -422,49 +1026,41 @@ impl From<InteractionResponse> for WebhookMessage {
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
 /// Reference to a message. Contains useful identifiers.
 pub struct MessageReference {
-    #[serde_as(as = "DisplayFromStr")]
-    message_id: Snowflake,
-    #[serde_as(as = "Option<DisplayFromStr>")]
-    #[serde(default)]
-    guild_id: Option<Snowflake>,
-    #[serde_as(as = "Option<DisplayFromStr>")]
-    #[serde(default)]
-    channel_id: Option<Snowflake>,
+    message_id: MessageId,
+    guild_id: Option<GuildId>,
+    channel_id: Option<ChannelId>,
 }
 
 impl MessageReference {
     /// Get the message id of this message
-    pub fn message_id(&self) -> Snowflake {
+    pub fn message_id(&self) -> MessageId {
         self.message_id
     }
     /// Get the guild id of this message
     ///
     /// `None` if message is in DM
-    pub fn guild_id(&self) -> Option<Snowflake> {
+    pub fn guild_id(&self) -> Option<GuildId> {
         self.guild_id
     }
 
     /// Get the channel ID of this message
     ///
     /// `None` if message is in DM
-    pub fn channel_id(&self) -> Option<Snowflake> {
+    pub fn channel_id(&self) -> Option<ChannelId> {
         self.channel_id
     }
 }
-#[cfg(feature = "handler")]
+#[cfg(feature = "handler-core")]
 #[serde_as]
 #[skip_serializing_none]
 #[derive(Clone, Serialize, Deserialize, Debug)]
 /// Read-only struct representing a Followup message sent by some application.
 pub struct FollowupMessage {
-    #[serde_as(as = "DisplayFromStr")]
-    id: Snowflake,
+    id: MessageId,
     r#type: u8,
     content: Option<String>,
     embeds: Vec<Embed>,
-    #[serde_as(as = "Option<DisplayFromStr>")]
-    #[serde(default)]
-    channel_id: Option<Snowflake>,
+    channel_id: Option<ChannelId>,
     author: Option<User>,
     tts: bool,
     #[serde_as(as = "DisplayFromStr")]
@@ -472,23 +1068,23 @@ pub struct FollowupMessage {
     #[serde_as(as = "Option<DisplayFromStr>")]
     #[serde(default)]
     edited_timestamp: Option<DateTime<Utc>>,
-    flags: u32,
-    #[serde_as(as = "DisplayFromStr")]
-    application_id: Snowflake,
-    #[serde_as(as = "DisplayFromStr")]
-    webhook_id: Snowflake,
+    flags: MessageFlags,
+    application_id: ApplicationId,
+    webhook_id: WebhookId,
     message_reference: MessageReference,
 
     #[serde(skip)]
     interaction_token: String,
     #[serde(skip)]
     client: Client,
+    #[serde(skip)]
+    default_allowed_mentions: Option<AllowedMentions>,
 }
-#[cfg(feature = "handler")]
+#[cfg(feature = "handler-core")]
 /// Getter functions
 impl FollowupMessage {
     /// Get the ID of this follow up
-    pub fn id(&self) -> Snowflake {
+    pub fn id(&self) -> MessageId {
         self.id
     }
     /// Get the type of message of this follow up
@@ -517,17 +1113,17 @@ impl FollowupMessage {
     }
 
     /// Get the message flags of this message
-    pub fn flags(&self) -> u32 {
+    pub fn flags(&self) -> MessageFlags {
         self.flags
     }
 
     /// Get the application id of the application that made this message
-    pub fn app_id(&self) -> Snowflake {
+    pub fn app_id(&self) -> ApplicationId {
         self.application_id
     }
 
     /// Get the webhook id associated with this message
-    pub fn webhook_id(&self) -> Snowflake {
+    pub fn webhook_id(&self) -> WebhookId {
         self.webhook_id
     }
 
@@ -539,17 +1135,22 @@ impl FollowupMessage {
     }
 }
 
-#[cfg(feature = "handler")]
+#[cfg(feature = "handler-core")]
 /// 'Do' functions
 impl FollowupMessage {
     /// Edit this followup message
     pub async fn edit_message(&mut self, new_content: &WebhookMessage) -> Result<(), HttpError> {
         let url = format!(
-            "/webhooks/{:?}/{:?}/messages/{:?}",
-            self.application_id, self.interaction_token, self.id
+            "{}/webhooks/{}/{}/messages/{}",
+            crate::BASE_URL,
+            self.application_id,
+            self.interaction_token,
+            self.id
         );
 
-        let exec = self.client.post(&url).json(new_content).send().await;
+        new_content.validate_for_sending()?;
+        let new_content = new_content.or_default_mentions(self.default_allowed_mentions.as_ref());
+        let exec = self.client.patch(&url).json(&new_content).send().await;
 
         expect_successful_api_response!(exec, {
             // TODO: Update edited fields
@@ -565,7 +1166,7 @@ impl FollowupMessage {
     /// Errors get printed using the [`debug!`] macro
     pub async fn delete_message(self) -> Result<(), Self> {
         let url = format!(
-            "{}/webhooks/{:?}/{}/messages/{:?}",
+            "{}/webhooks/{}/{}/messages/{}",
             crate::BASE_URL,
             self.application_id,
             self.interaction_token,
@@ -596,7 +1197,7 @@ impl FollowupMessage {
     }
 }
 
-#[cfg(feature = "handler")]
+#[cfg(feature = "handler-core")]
 impl Context {
     /// Creates a new [`Context`]
     pub fn new(c: Client, i: Interaction) -> Self {
@@ -618,6 +1219,7 @@ impl Context {
             client: c,
             interaction: i,
             author_id: user_id,
+            default_allowed_mentions: None,
         }
     }
 
@@ -629,11 +1231,14 @@ impl Context {
     /// ```ignore
     /// let response = ctx.respond()
     ///                   .content("Example message")
-    ///                   .tts(true)
-    ///                   .finish();
+    ///                   .tts(&true)
+    ///                   .build()?;
     /// ```
     pub fn respond(&self) -> InteractionResponseBuilder {
-        let mut b = InteractionResponseBuilder::default();
+        let mut b = InteractionResponseBuilder {
+            interaction_type: Some(self.interaction.r#type.clone()),
+            ..Default::default()
+        };
 
         // Default to UpdateMessage response type if InteractionType is MessageComponent
         if self.interaction.r#type == InteractionType::MessageComponent {
@@ -643,17 +1248,94 @@ impl Context {
         b
     }
 
+    // An ephemeral reply, like the error reply of a guard or extractor. Always a new message,
+    // so a component's message is not replaced by it
+    pub(crate) fn ephemeral_reply(&self, content: impl ToString) -> InteractionResponse {
+        self.respond()
+            .respond_type(InteractionResponseType::ChannelMessageWithSource)
+            .content(content)
+            .is_ephemeral(true)
+            .build()
+            .unwrap_or_else(|e| {
+                error!("Failed to build ephemeral reply: {}", e);
+                self.respond().none()
+            })
+    }
+
+    /// Check whether the invoking user or guild has an active entitlement for the given SKU
+    pub fn has_entitlement(&self, sku: impl Into<SkuId>) -> bool {
+        let sku = sku.into();
+
+        self.interaction
+            .entitlements
+            .as_ref()
+            .map(|e| e.iter().any(|e| e.sku_id == sku && e.is_active()))
+            .unwrap_or(false)
+    }
+
+    /// Get the values that were selected in a select menu.
+    ///
+    /// For auto-populated select menus, these are the IDs of the selected users, roles or channels.
+    pub fn selected_values(&self) -> Vec<String> {
+        self.interaction
+            .data
+            .as_ref()
+            .map(|d| d.selected_values().to_vec())
+            .unwrap_or_default()
+    }
+
+    /// Get the value of a text input from a submitted modal by its `custom_id`
+    pub fn modal_value(&self, custom_id: &str) -> Option<String> {
+        self.interaction
+            .data
+            .as_ref()?
+            .modal_value(custom_id)
+            .map(String::from)
+    }
+
+    fn resolved(&self) -> Option<&ResolvedData> {
+        self.interaction.data.as_ref()?.resolved.as_ref()
+    }
+
+    /// Get the users that were selected in a user or mentionable select menu.
+    ///
+    /// This is resolved from the data Discord sent with the interaction.
+    pub fn selected_users(&self) -> Vec<User> {
+        self.resolved()
+            .and_then(|r| r.users.as_ref())
+            .map(|u| u.values().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Get the roles that were selected in a role or mentionable select menu.
+    pub fn selected_roles(&self) -> Vec<Role> {
+        self.resolved()
+            .and_then(|r| r.roles.as_ref())
+            .map(|r| r.values().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Get the channels that were selected in a channel select menu.
+    pub fn selected_channels(&self) -> Vec<PartialChannel> {
+        self.resolved()
+            .and_then(|r| r.channels.as_ref())
+            .map(|c| c.values().cloned().collect())
+            .unwrap_or_default()
+    }
+
     /// Edit the original interaction response
     ///
     /// This takes an [`WebhookMessage`]. You can convert an [`InteractionResponse`] using [`WebhookMessage::from`].
     pub async fn edit_original(&self, new_content: &WebhookMessage) -> Result<(), HttpError> {
         let url = format!(
-            "{}/webhooks/{:?}/{}/messages/@original",
+            "{}/webhooks/{}/{}/messages/@original",
             crate::BASE_URL,
             self.interaction.application_id.unwrap(),
             self.interaction.token.as_ref().unwrap()
         );
-        let c = self.client.patch(&url).json(new_content).send().await;
+        new_content.validate_for_sending()?;
+        let new_content = new_content.or_default_mentions(self.default_allowed_mentions.as_ref());
+        let c = self.client.patch(&url).json(&new_content).send().await;
 
         expect_successful_api_response!(c, Ok(()))
     }
@@ -661,7 +1343,7 @@ impl Context {
     /// Delete the original interaction response
     pub async fn delete_original(&self) -> Result<(), HttpError> {
         let url = format!(
-            "{}/webhooks/{:?}/{}/messages/@original",
+            "{}/webhooks/{}/{}/messages/@original",
             crate::BASE_URL,
             self.interaction.application_id.unwrap(),
             self.interaction.token.as_ref().unwrap()
@@ -677,13 +1359,15 @@ impl Context {
         content: &WebhookMessage,
     ) -> Result<FollowupMessage, HttpError> {
         let url = format!(
-            "{}/webhooks/{:?}/{}?wait=true",
+            "{}/webhooks/{}/{}?wait=true",
             crate::BASE_URL,
             self.interaction.application_id.unwrap(),
             self.interaction.token.as_ref().unwrap()
         );
 
-        let c = self.client.post(&url).json(content).send().await;
+        content.validate_for_sending()?;
+        let content = content.or_default_mentions(self.default_allowed_mentions.as_ref());
+        let c = self.client.post(&url).json(&content).send().await;
 
         match c {
             Err(e) => {
@@ -717,6 +1401,7 @@ impl Context {
                         Ok(mut f) => {
                             f.interaction_token =
                                 self.interaction.token.as_ref().unwrap().to_string();
+                            f.default_allowed_mentions = self.default_allowed_mentions.clone();
                             Ok(f)
                         }
                     }
@@ -730,12 +1415,8 @@ impl Context {
 /// Getter functions
 impl Context {
     /// Get a [`Guild`] from an ID
-    pub async fn get_guild<I: Into<Snowflake>>(&self, id: I) -> Result<Guild, HttpError> {
-        let url = format!(
-            "{}/guilds/{:?}?with_counts=true",
-            crate::BASE_URL,
-            id.into()
-        );
+    pub async fn get_guild<I: Into<GuildId>>(&self, id: I) -> Result<Guild, HttpError> {
+        let url = format!("{}/guilds/{}?with_counts=true", crate::BASE_URL, id.into());
 
         let r = self.client.get(&url).send().await;
         expect_successful_api_response_and_return!(r, Guild, g, Ok(g))
@@ -744,11 +1425,11 @@ impl Context {
     /// Get a [`Member`] from a [`Guild`]
     pub async fn get_guild_member(
         &self,
-        guild_id: impl Into<Snowflake>,
-        user_id: impl Into<Snowflake>,
+        guild_id: impl Into<GuildId>,
+        user_id: impl Into<UserId>,
     ) -> Result<Member, HttpError> {
         let url = format!(
-            "{}/guilds/{:?}/members/{:?}",
+            "{}/guilds/{}/members/{}",
             crate::BASE_URL,
             guild_id.into(),
             user_id.into()
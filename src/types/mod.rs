@@ -18,11 +18,27 @@ pub mod components;
 
 /// Module containing structures for guilds
 pub mod guild;
+
+/// Module containing structures for channels
+pub mod channel;
+
+/// Module containing structures for monetization (entitlements and SKUs)
+pub mod monetization;
 //use interaction::{InteractionResponse, Interaction};
 
+/// Module containing structures for modals
 pub mod modal;
+
+/// Module containing helpers for formatting and parsing mentions, timestamps, emojis and markdown
+pub mod format;
 mod attachment;
 
+/// Module containing helpers to build image URLs on Discord's CDN
+pub mod cdn;
+
+/// Module containing typed IDs, such as [`UserId`](id::UserId) and [`GuildId`](id::GuildId)
+pub mod id;
+
 /// Discord's 'snowflake'. It's a 64bit unsigned integer that is mainly used for identifying anything Discord.
 pub type Snowflake = u64;
 
@@ -1,11 +1,41 @@
 use serde::{Deserialize, Serialize};
 
-use super::components::MessageComponent;
+use super::components::{ComponentType, MessageComponent};
 use serde_with::*;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
+/// A modal (popup form) that can be shown to a user
 pub struct Modal {
     custom_id: String,
     title: String,
     components: Vec<MessageComponent>,
 }
+
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+/// A component as submitted with a modal. Action rows contain the submitted text inputs.
+pub struct ModalSubmitComponent {
+    /// Type of component
+    pub r#type: ComponentType,
+    /// The custom identifier of the text input
+    pub custom_id: Option<String>,
+    /// The value the user filled in
+    pub value: Option<String>,
+    /// Child components of an action row
+    pub components: Option<Vec<ModalSubmitComponent>>,
+}
+
+impl ModalSubmitComponent {
+    /// Find the value of the input with the given `custom_id`, searching child components as well.
+    pub fn find_value(&self, custom_id: &str) -> Option<&str> {
+        if self.custom_id.as_deref() == Some(custom_id) {
+            return self.value.as_deref();
+        }
+
+        self.components
+            .as_ref()?
+            .iter()
+            .find_map(|c| c.find_value(custom_id))
+    }
+}
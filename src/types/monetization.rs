@@ -0,0 +1,205 @@
+use serde::{Deserialize, Serialize};
+
+use ::chrono::{DateTime, Utc};
+use serde_repr::*;
+use serde_with::*;
+
+use super::id::{ApplicationId, EntitlementId, GuildId, SkuId, UserId};
+use super::Snowflake;
+
+#[derive(Clone, Copy, Serialize_repr, Deserialize_repr, PartialEq, Eq, Debug)]
+#[repr(u8)]
+#[non_exhaustive]
+/// Represents the type of an [`Entitlement`]
+pub enum EntitlementType {
+    /// Entitlement was purchased by user
+    Purchase = 1,
+    /// Entitlement for Discord Nitro subscription
+    PremiumSubscription = 2,
+    /// Entitlement was gifted by developer
+    DeveloperGift = 3,
+    /// Entitlement was purchased by a dev in application test mode
+    TestModePurchase = 4,
+    /// Entitlement was granted when the SKU was free
+    FreePurchase = 5,
+    /// Entitlement was gifted by another user
+    UserGift = 6,
+    /// Entitlement was claimed by user for free as a Nitro Subscriber
+    PremiumPurchase = 7,
+    /// Entitlement was purchased as an app subscription
+    ApplicationSubscription = 8,
+}
+
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Clone, Serialize, Deserialize, Debug)]
+/// An entitlement represents that a user or guild has access to a premium offering ([`Sku`]) in your application.
+pub struct Entitlement {
+    /// ID of the entitlement
+    pub id: EntitlementId,
+    /// ID of the SKU
+    pub sku_id: SkuId,
+    /// ID of the parent application
+    pub application_id: ApplicationId,
+    /// ID of the user that is granted access to the entitlement's SKU
+    pub user_id: Option<UserId>,
+    /// ID of the guild that is granted access to the entitlement's SKU
+    pub guild_id: Option<GuildId>,
+    /// Type of entitlement
+    pub r#type: EntitlementType,
+    /// Entitlement was deleted
+    pub deleted: bool,
+    /// Start date at which the entitlement is valid
+    pub starts_at: Option<DateTime<Utc>>,
+    /// Date at which the entitlement is no longer valid
+    pub ends_at: Option<DateTime<Utc>>,
+    /// For consumable items, whether or not the entitlement has been consumed
+    pub consumed: Option<bool>,
+}
+
+impl Entitlement {
+    /// Whether this entitlement currently grants access to its SKU.
+    ///
+    /// An entitlement is active if it was not deleted or consumed, and the current time is within its validity period.
+    pub fn is_active(&self) -> bool {
+        let now = Utc::now();
+
+        !self.deleted
+            && !self.consumed.unwrap_or(false)
+            && self.starts_at.is_none_or(|s| s <= now)
+            && self.ends_at.is_none_or(|e| e > now)
+    }
+}
+
+impl PartialEq for Entitlement {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+#[derive(Clone, Copy, Serialize_repr, Deserialize_repr, PartialEq, Eq, Debug)]
+#[repr(u8)]
+#[non_exhaustive]
+/// Represents the type of a [`Sku`]
+pub enum SkuType {
+    /// Durable one-time purchase
+    Durable = 2,
+    /// Consumable one-time purchase
+    Consumable = 3,
+    /// Represents a recurring subscription
+    Subscription = 5,
+    /// System-generated group for each `Subscription` SKU created
+    SubscriptionGroup = 6,
+}
+
+#[serde_as]
+#[derive(Clone, Serialize, Deserialize, Debug)]
+/// A SKU (stock-keeping unit) represents a premium offering that can be made available to your application's users or guilds.
+pub struct Sku {
+    /// ID of the SKU
+    pub id: SkuId,
+    /// Type of SKU
+    pub r#type: SkuType,
+    /// ID of the parent application
+    pub application_id: ApplicationId,
+    /// Customer-facing name of your premium offering
+    pub name: String,
+    /// System-generated URL slug based on the SKU's name
+    pub slug: String,
+    /// [SKU flags](https://discord.com/developers/docs/monetization/skus#sku-object-sku-flags) combined as a bitfield
+    pub flags: u32,
+}
+
+impl PartialEq for Sku {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+#[derive(Clone, Copy, Serialize_repr, Deserialize_repr, PartialEq, Eq, Debug)]
+#[repr(u8)]
+/// The owner type of a test [`Entitlement`]
+pub enum EntitlementOwnerType {
+    /// A guild subscription
+    Guild = 1,
+    /// A user subscription
+    User = 2,
+}
+
+#[serde_as]
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+/// Used to create a test [`Entitlement`]
+pub struct TestEntitlement {
+    /// ID of the SKU to grant the entitlement to
+    pub sku_id: SkuId,
+    #[serde_as(as = "DisplayFromStr")]
+    /// ID of the guild or user to grant the entitlement to
+    pub owner_id: Snowflake,
+    /// Whether the owner is a guild or a user
+    pub owner_type: EntitlementOwnerType,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+/// Filter used when listing the entitlements of an application
+pub struct EntitlementFilter {
+    user_id: Option<UserId>,
+    guild_id: Option<GuildId>,
+    sku_ids: Vec<SkuId>,
+    exclude_ended: Option<bool>,
+    exclude_deleted: Option<bool>,
+}
+
+impl EntitlementFilter {
+    /// Only list entitlements of this user
+    pub fn user_id(mut self, id: impl Into<UserId>) -> Self {
+        self.user_id = Some(id.into());
+        self
+    }
+
+    /// Only list entitlements of this guild
+    pub fn guild_id(mut self, id: impl Into<GuildId>) -> Self {
+        self.guild_id = Some(id.into());
+        self
+    }
+
+    /// Only list entitlements for this SKU. Can be called multiple times.
+    pub fn add_sku(mut self, id: impl Into<SkuId>) -> Self {
+        self.sku_ids.push(id.into());
+        self
+    }
+
+    /// Whether or not ended entitlements should be omitted
+    pub fn exclude_ended(mut self, exclude: bool) -> Self {
+        self.exclude_ended = Some(exclude);
+        self
+    }
+
+    /// Whether or not deleted entitlements should be omitted
+    pub fn exclude_deleted(mut self, exclude: bool) -> Self {
+        self.exclude_deleted = Some(exclude);
+        self
+    }
+
+    /// Converts the filter to query parameters
+    pub fn to_query(&self) -> Vec<(&'static str, String)> {
+        let mut q = Vec::new();
+
+        if let Some(u) = self.user_id {
+            q.push(("user_id", u.to_string()));
+        }
+        if let Some(g) = self.guild_id {
+            q.push(("guild_id", g.to_string()));
+        }
+        if !self.sku_ids.is_empty() {
+            let skus: Vec<String> = self.sku_ids.iter().map(|s| s.to_string()).collect();
+            q.push(("sku_ids", skus.join(",")));
+        }
+        if let Some(e) = self.exclude_ended {
+            q.push(("exclude_ended", e.to_string()));
+        }
+        if let Some(d) = self.exclude_deleted {
+            q.push(("exclude_deleted", d.to_string()));
+        }
+        q
+    }
+}
@@ -3,7 +3,8 @@ use serde::{Deserialize, Serialize};
 use ::chrono::{DateTime, Utc};
 use serde_with::*;
 
-use super::Snowflake;
+use super::guild::Permissions;
+use super::id::{RoleId, SkuId, UserId};
 
 // ======= STRUCTS =======
 
@@ -11,14 +12,15 @@ use super::Snowflake;
 #[derive(Clone, Serialize, Deserialize, Debug)]
 /// A Discord user
 pub struct User {
-    #[serde_as(as = "DisplayFromStr")]
     /// User id
-    pub id: Snowflake,
-    /// The username
+    pub id: UserId,
+    /// The username, unique across the platform
     pub username: String,
-    /// The discriminator. (Ex. `#1337`)
-    pub discriminator: String,
-    /// URL to their avatar/profile picture
+    /// The legacy discriminator (Ex. `1337`). `"0"` for users on the new username system
+    pub discriminator: Option<String>,
+    /// The display name, if set. Use [`User::display_name`] to get the name shown in the client
+    pub global_name: Option<String>,
+    /// Avatar hash. Use [`User::avatar_url`] to get the URL
     pub avatar: Option<String>,
     /// Is it a bot?
     pub bot: Option<bool>,
@@ -34,13 +36,33 @@ pub struct User {
     pub email: Option<String>,
     /// Flags set on user
     pub flags: Option<i32>,
-    #[serde_as(as = "Option<DisplayFromStr>")]
-    #[serde(default)]
     /// Type of nitro subscription
     pub premium_type: Option<i8>,
 
     /// Public flags for user
     pub public_flags: Option<i32>,
+    /// Banner hash. Use [`User::banner_url`] to get the URL
+    pub banner: Option<String>,
+    /// Banner color as an integer representation of a hex color code
+    pub accent_color: Option<u32>,
+    /// Data for the user's avatar decoration
+    pub avatar_decoration_data: Option<AvatarDecorationData>,
+}
+
+impl User {
+    /// Get the name shown in the client: the display name if set, otherwise the username
+    pub fn display_name(&self) -> &str {
+        self.global_name.as_deref().unwrap_or(&self.username)
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+/// The decoration displayed around a user's avatar
+pub struct AvatarDecorationData {
+    /// The avatar decoration hash
+    pub asset: String,
+    /// ID of the avatar decoration's SKU
+    pub sku_id: SkuId,
 }
 
 impl PartialEq for User {
@@ -58,22 +80,83 @@ pub struct Member {
     pub user: User,
     /// The member's nickname, if any
     pub nick: Option<String>,
-    #[serde_as(as = "Vec<DisplayFromStr>")]
+    /// The member's guild avatar hash. Use [`Member::avatar_url`] to get the URL
+    pub avatar: Option<String>,
+    /// The member's guild banner hash
+    pub banner: Option<String>,
     /// The member's assigned roles
-    pub roles: Vec<Snowflake>,
+    pub roles: Vec<RoleId>,
     /// When this user joined
     pub joined_at: DateTime<Utc>,
 
     /// When the member started boosting the server, if boosting
     pub premium_since: Option<DateTime<Utc>>,
+    #[serde(default)]
     /// Is this member server deafened?
     pub deaf: bool,
+    #[serde(default)]
     /// Is this member server muted (voice)?
     pub mute: bool,
-    /// Pending status
+    #[serde(default)]
+    /// Whether the user has not yet passed the guild's membership screening requirements
     pub pending: bool,
-    /// Permission overrides?
+    /// Total permissions of the member in the channel, including overwrites. Only present in interactions
+    pub permissions: Option<String>,
+    /// When the member's timeout will expire, if timed out
+    pub communication_disabled_until: Option<DateTime<Utc>>,
+    #[serde(default)]
+    /// [Guild member flags](https://discord.com/developers/docs/resources/guild#guild-member-object-guild-member-flags) combined as a bitfield
+    pub flags: u32,
+}
+
+impl Member {
+    /// Parse the permissions of the member in the channel. Only present in interactions
+    pub fn permission_set(&self) -> Option<Permissions> {
+        self.permissions.as_ref()?.parse().ok()
+    }
+
+    /// Get the name shown in the guild: the nickname if set, otherwise the user's display name or username
+    pub fn display_name(&self) -> &str {
+        self.nick
+            .as_deref()
+            .unwrap_or_else(|| self.user.display_name())
+    }
+
+    /// Whether the member is currently timed out
+    pub fn is_timed_out(&self) -> bool {
+        self.communication_disabled_until
+            .is_some_and(|t| t > Utc::now())
+    }
+}
+
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+/// A partial [`Member`], as found in resolved interaction data.
+///
+/// The associated [`User`] can be found in the resolved users with the same ID.
+pub struct PartialMember {
+    /// The member's nickname, if any
+    pub nick: Option<String>,
+    /// The member's guild avatar hash
+    pub avatar: Option<String>,
+    /// The member's guild banner hash
+    pub banner: Option<String>,
+    /// The member's assigned roles
+    pub roles: Vec<RoleId>,
+    /// When this user joined
+    pub joined_at: DateTime<Utc>,
+    /// When the member started boosting the server, if boosting
+    pub premium_since: Option<DateTime<Utc>>,
+    /// Pending status
+    pub pending: Option<bool>,
+    /// Total permissions of the member in the channel, including overwrites
     pub permissions: Option<String>,
+    /// When the member's timeout will expire, if timed out
+    pub communication_disabled_until: Option<DateTime<Utc>>,
+    #[serde(default)]
+    /// [Guild member flags](https://discord.com/developers/docs/resources/guild#guild-member-object-guild-member-flags) combined as a bitfield
+    pub flags: u32,
 }
 
 impl From<Member> for User {